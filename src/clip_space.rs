@@ -0,0 +1,89 @@
+/// The Range that depth values are mapped to in normalized device coordinates
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DepthRange {
+	/// Depth is mapped to `[-1, 1]`, as used by OpenGL
+	NegativeOneToOne,
+	/// Depth is mapped to `[0, 1]`, as used by Vulkan, Direct3D and Metal
+	ZeroToOne,
+}
+
+/// The Handedness of the view space Coordinate System
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Handedness {
+	/// x points right, y points up and the Camera looks along -z, as used by OpenGL
+	Right,
+	/// x points right, y points up and the Camera looks along +z, as used by Direct3D
+	Left,
+}
+
+/// The conventions that a Projection Matrix should follow
+///
+/// The default is the OpenGL convention: `[-1, 1]` depth, right-handed and no reverse-Z.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ClipSpace {
+	/// The Range that depth values are mapped to
+	pub depth: DepthRange,
+	/// The Handedness of the view space
+	pub handedness: Handedness,
+	/// Map the near plane to the upper end of the depth Range and the far plane to the lower end
+	///
+	/// Combined with [`DepthRange::ZeroToOne`](enum.DepthRange.html) and a floating point depth
+	/// buffer, this gives a much more even distribution of depth precision.
+	pub reverse_z: bool,
+}
+
+impl ClipSpace {
+	/// The OpenGL convention: `[-1, 1]` depth and right-handed
+	pub const OPENGL: ClipSpace = ClipSpace {
+		depth: DepthRange::NegativeOneToOne,
+		handedness: Handedness::Right,
+		reverse_z: false,
+	};
+	/// The Vulkan convention: `[0, 1]` depth and right-handed
+	///
+	/// Note that Vulkan's y Axis points down in clip space, which is not handled here.
+	pub const VULKAN: ClipSpace = ClipSpace {
+		depth: DepthRange::ZeroToOne,
+		handedness: Handedness::Right,
+		reverse_z: false,
+	};
+	/// The Direct3D convention: `[0, 1]` depth and left-handed
+	pub const DIRECT3D: ClipSpace = ClipSpace {
+		depth: DepthRange::ZeroToOne,
+		handedness: Handedness::Left,
+		reverse_z: false,
+	};
+
+	/// Returns the same conventions with reverse-Z enabled
+	pub const fn reversed(self) -> ClipSpace {
+		ClipSpace {
+			reverse_z: true,
+			..self
+		}
+	}
+	/// Returns the depth values that the near and far plane are mapped to
+	pub fn depth_bounds(self) -> (f64, f64) {
+		let (low, high) = match self.depth {
+			DepthRange::NegativeOneToOne => (-1.0, 1.0),
+			DepthRange::ZeroToOne => (0.0, 1.0),
+		};
+		if self.reverse_z {
+			(high, low)
+		} else {
+			(low, high)
+		}
+	}
+	/// Returns the sign of the view space z Axis in the looking direction
+	pub fn forward_z(self) -> f64 {
+		match self.handedness {
+			Handedness::Right => -1.0,
+			Handedness::Left => 1.0,
+		}
+	}
+}
+
+impl Default for ClipSpace {
+	fn default() -> ClipSpace {
+		ClipSpace::OPENGL
+	}
+}
@@ -0,0 +1,80 @@
+/// The order in which Euler angle Rotations are applied
+///
+/// Each variant names the Axes in the order their Rotations are applied, around the fixed
+/// (world) Axes. So `XYZ` first rotates around x, then around y and finally around z, which is
+/// the same as `Matrix::rot_z(c) * Matrix::rot_y(b) * Matrix::rot_x(a)`.
+///
+/// This is equivalent to rotating around the Axes of the rotated Object (intrinsic Rotations)
+/// in the reverse order.
+///
+/// The first six variants are the Tait-Bryan orders, which use every Axis once. The last six
+/// are the proper Euler orders, which use the first Axis twice.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum EulerOrder {
+	/// x, then y, then z
+	XYZ,
+	/// x, then z, then y
+	XZY,
+	/// y, then x, then z
+	YXZ,
+	/// y, then z, then x
+	YZX,
+	/// z, then x, then y
+	ZXY,
+	/// z, then y, then x
+	ZYX,
+	/// x, then y, then x
+	XYX,
+	/// x, then z, then x
+	XZX,
+	/// y, then x, then y
+	YXY,
+	/// y, then z, then y
+	YZY,
+	/// z, then x, then z
+	ZXZ,
+	/// z, then y, then z
+	ZYZ,
+}
+
+impl EulerOrder {
+	/// All twelve Euler orders
+	pub const ALL: [EulerOrder; 12] = [
+		EulerOrder::XYZ,
+		EulerOrder::XZY,
+		EulerOrder::YXZ,
+		EulerOrder::YZX,
+		EulerOrder::ZXY,
+		EulerOrder::ZYX,
+		EulerOrder::XYX,
+		EulerOrder::XZX,
+		EulerOrder::YXY,
+		EulerOrder::YZY,
+		EulerOrder::ZXZ,
+		EulerOrder::ZYZ,
+	];
+
+	/// Returns the indices (`0` = x, `1` = y, `2` = z) of the Axes in the order they are applied
+	pub fn axes(self) -> [usize; 3] {
+		use self::EulerOrder::*;
+		match self {
+			XYZ => [0, 1, 2],
+			XZY => [0, 2, 1],
+			YXZ => [1, 0, 2],
+			YZX => [1, 2, 0],
+			ZXY => [2, 0, 1],
+			ZYX => [2, 1, 0],
+			XYX => [0, 1, 0],
+			XZX => [0, 2, 0],
+			YXY => [1, 0, 1],
+			YZY => [1, 2, 1],
+			ZXZ => [2, 0, 2],
+			ZYZ => [2, 1, 2],
+		}
+	}
+	/// Checks if this is a proper Euler order, where the first and last Axis are the same
+	pub fn is_proper(self) -> bool {
+		let axes = self.axes();
+		axes[0] == axes[2]
+	}
+}
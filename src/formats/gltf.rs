@@ -0,0 +1,908 @@
+//! Importing of [glTF 2.0](https://www.khronos.org/gltf/) files
+//!
+//! Both the JSON based `.gltf` and the binary `.glb` variant are supported. Buffers can be
+//! embedded as `data:` URIs, stored in the binary chunk of a `.glb` file or in local files next
+//! to the loaded file. Only the geometry, the node hierarchy and the basic PBR parameters of the
+//! materials are imported; textures, animations, skins and cameras are ignored.
+
+use std::fs::File;
+use std::io::Read;
+use std::path::{Component, Path, PathBuf};
+
+use formats::json::Json;
+use formats::Error;
+use matrix::Mat4;
+use quaternion::Quat;
+use shapes::Mesh;
+use vector::Vec3;
+use vector2::Vec2;
+use vector4::Vec4;
+use Scalar;
+
+/// The content of a glTF file
+#[derive(Clone, Debug)]
+pub struct Gltf<T: Scalar = f32> {
+	/// All nodes of the file, referencing each other by index
+	pub nodes: Vec<GltfNode<T>>,
+	/// All meshes of the file, referenced by the nodes
+	pub meshes: Vec<GltfMesh<T>>,
+	/// All materials of the file, referenced by the primitives of the meshes
+	pub materials: Vec<GltfMaterial<T>>,
+	/// All scenes of the file
+	pub scenes: Vec<GltfScene>,
+	/// The index of the scene that should be displayed by default (_optional_)
+	pub scene: Option<usize>,
+}
+
+/// A scene, consisting of a list of root nodes
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct GltfScene {
+	/// The name of the scene, or an empty String if there is none
+	pub name: String,
+	/// The indices of the root nodes of the scene
+	pub nodes: Vec<usize>,
+}
+
+/// A node in the hierarchy of a glTF file
+#[derive(Clone, Debug)]
+pub struct GltfNode<T: Scalar = f32> {
+	/// The name of the node, or an empty String if there is none
+	pub name: String,
+	/// The transformation of the node relative to its parent
+	pub transform: NodeTransform<T>,
+	/// The index of the mesh of the node (_optional_)
+	pub mesh: Option<usize>,
+	/// The indices of the child nodes
+	pub children: Vec<usize>,
+}
+
+/// The local transformation of a node, as stored in the file
+#[derive(Clone, Copy, Debug)]
+pub enum NodeTransform<T: Scalar = f32> {
+	/// An arbitrary Matrix
+	Matrix(Mat4<T>),
+	/// A translation, rotation and scale, applied in reverse order
+	Trs {
+		/// The translation
+		translation: Vec3<T>,
+		/// The rotation
+		rotation: Quat<T>,
+		/// The scale along each Axis
+		scale: Vec3<T>,
+	},
+}
+
+/// A mesh of a glTF file, consisting of multiple primitives with different materials
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct GltfMesh<T: Scalar = f32> {
+	/// The name of the mesh, or an empty String if there is none
+	pub name: String,
+	/// The parts of the mesh
+	pub primitives: Vec<GltfPrimitive<T>>,
+}
+
+/// A part of a glTF mesh with a single material
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct GltfPrimitive<T: Scalar = f32> {
+	/// The geometry, including normals and the first set of texture coordinates if they exist
+	pub mesh: Mesh<T>,
+	/// The index of the material (_optional_)
+	pub material: Option<usize>,
+}
+
+/// How the alpha value of a material is interpreted
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AlphaMode {
+	/// The alpha value is ignored
+	Opaque,
+	/// The material is either fully opaque or fully transparent, depending on the alpha cutoff
+	Mask,
+	/// The alpha value is used for blending
+	Blend,
+}
+
+/// The basic parameters of a metallic-roughness PBR material
+#[derive(Clone, Debug, PartialEq)]
+pub struct GltfMaterial<T: Scalar = f32> {
+	/// The name of the material, or an empty String if there is none
+	pub name: String,
+	/// The linear base color as `(red, green, blue, alpha)`
+	pub base_color: Vec4<T>,
+	/// How metallic the material is, from 0 to 1
+	pub metallic: T,
+	/// How rough the material is, from 0 to 1
+	pub roughness: T,
+	/// The light emitted by the material as `(red, green, blue)`
+	pub emissive: Vec3<T>,
+	/// How the alpha value is interpreted
+	pub alpha_mode: AlphaMode,
+	/// The alpha value below which the material is transparent in [Mask](enum.AlphaMode.html#variant.Mask) mode
+	pub alpha_cutoff: T,
+	/// Whether back faces should be rendered
+	pub double_sided: bool,
+}
+
+impl<T: Scalar> Default for GltfMaterial<T> {
+	/// The default material of the glTF specification: white, fully metallic and fully rough
+	fn default() -> GltfMaterial<T> {
+		GltfMaterial {
+			name: String::new(),
+			base_color: Vec4 {
+				x: T::ONE,
+				y: T::ONE,
+				z: T::ONE,
+				w: T::ONE,
+			},
+			metallic: T::ONE,
+			roughness: T::ONE,
+			emissive: Vec3::new(),
+			alpha_mode: AlphaMode::Opaque,
+			alpha_cutoff: T::from_f64(0.5),
+			double_sided: false,
+		}
+	}
+}
+
+impl<T: Scalar> NodeTransform<T> {
+	/// calculates the Matrix of the transformation
+	pub fn matrix(&self) -> Mat4<T> {
+		match *self {
+			NodeTransform::Matrix(matrix) => matrix,
+			NodeTransform::Trs {
+				translation,
+				rotation,
+				scale,
+			} => Mat4::from_trs(translation, rotation, scale),
+		}
+	}
+}
+
+/// The magic number at the start of a `.glb` file
+const GLB_MAGIC: &[u8] = b"glTF";
+/// The type of the chunk that contains the JSON structure
+const CHUNK_JSON: u32 = 0x4E4F_534A;
+/// The type of the chunk that contains the binary buffer
+const CHUNK_BIN: u32 = 0x004E_4942;
+
+impl<T: Scalar> Gltf<T> {
+	/// reads the `.gltf` or `.glb` file at `path`
+	///
+	/// External buffers are loaded relative to the directory of the file
+	pub fn load<P: AsRef<Path>>(path: P) -> Result<Gltf<T>, Error> {
+		let path = path.as_ref();
+		let mut bytes = vec![];
+		File::open(path)?.read_to_end(&mut bytes)?;
+		Gltf::parse(&bytes, path.parent())
+	}
+	/// parses the content of a `.gltf` or `.glb` file
+	///
+	/// External buffers are loaded relative to `directory` and can't be outside of it. If it is None, only embedded buffers are allowed.
+	pub fn parse(bytes: &[u8], directory: Option<&Path>) -> Result<Gltf<T>, Error> {
+		let (json, binary) = if bytes.starts_with(GLB_MAGIC) {
+			split_glb(bytes)?
+		} else {
+			(bytes, None)
+		};
+		let json = ::std::str::from_utf8(json).map_err(|_| Error::Invalid("glTF JSON is not valid UTF-8".to_string()))?;
+		let root = Json::parse(json)?;
+		let version = root.get("asset").and_then(|a| a.get("version")).and_then(Json::as_str);
+		if !version.is_some_and(|v| v.starts_with("2.")) {
+			return Err(Error::Invalid(format!("unsupported glTF version {:?}", version)));
+		}
+		let buffers = array(&root, "buffers")
+			.iter()
+			.enumerate()
+			.map(|(i, buffer)| load_buffer(i, buffer, binary, directory))
+			.collect::<Result<Vec<_>, Error>>()?;
+		let input_length = buffers.iter().fold(json.len(), |sum, buffer| sum + buffer.len());
+		let reader = AccessorReader {
+			root: &root,
+			buffers,
+			input_length,
+		};
+
+		let meshes = array(&root, "meshes")
+			.iter()
+			.enumerate()
+			.map(|(i, mesh)| reader.mesh(i, mesh))
+			.collect::<Result<Vec<_>, Error>>()?;
+		let materials = array(&root, "materials").iter().map(parse_material).collect();
+		let nodes = array(&root, "nodes")
+			.iter()
+			.enumerate()
+			.map(|(i, node)| parse_node(i, node))
+			.collect::<Result<Vec<_>, Error>>()?;
+		let scenes = array(&root, "scenes")
+			.iter()
+			.map(|scene| GltfScene {
+				name: string(scene, "name"),
+				nodes: indices(scene, "nodes"),
+			})
+			.collect();
+		let gltf = Gltf {
+			nodes,
+			meshes,
+			materials,
+			scenes,
+			scene: root.get("scene").and_then(Json::as_usize),
+		};
+		gltf.validate()?;
+		Ok(gltf)
+	}
+	/// checks that all references are valid and that the nodes form a forest
+	fn validate(&self) -> Result<(), Error> {
+		let invalid = |what: &str, index: usize| Err(Error::Invalid(format!("invalid {} index {}", what, index)));
+		let mut has_parent = vec![false; self.nodes.len()];
+		for node in &self.nodes {
+			if let Some(mesh) = node.mesh.filter(|&m| m >= self.meshes.len()) {
+				return invalid("mesh", mesh);
+			}
+			for &child in &node.children {
+				if child >= self.nodes.len() || has_parent[child] {
+					return invalid("child node", child);
+				}
+				has_parent[child] = true;
+			}
+		}
+		// with at most one parent per node, a cycle is a set of nodes that can't be reached from a root
+		let mut reachable = 0;
+		let mut stack: Vec<usize> = (0..self.nodes.len()).filter(|&i| !has_parent[i]).collect();
+		while let Some(node) = stack.pop() {
+			reachable += 1;
+			stack.extend_from_slice(&self.nodes[node].children);
+		}
+		if reachable != self.nodes.len() {
+			return Err(Error::Invalid("the node hierarchy contains a cycle".to_string()));
+		}
+		for mesh in &self.meshes {
+			for primitive in &mesh.primitives {
+				if let Some(material) = primitive.material.filter(|&m| m >= self.materials.len()) {
+					return invalid("material", material);
+				}
+			}
+		}
+		for scene in &self.scenes {
+			if let Some(&node) = scene.nodes.iter().find(|&&n| n >= self.nodes.len()) {
+				return invalid("node", node);
+			}
+		}
+		match self.scene {
+			Some(scene) if scene >= self.scenes.len() => invalid("scene", scene),
+			_ => Ok(()),
+		}
+	}
+	/// Returns the root nodes of the scene that should be displayed
+	///
+	/// This is the default scene, or the first scene if there is no default. If there are no
+	/// scenes at all, all nodes without a parent are returned.
+	pub fn root_nodes(&self) -> Vec<usize> {
+		match self.scene.or(if self.scenes.is_empty() { None } else { Some(0) }) {
+			Some(scene) => self.scenes[scene].nodes.clone(),
+			None => {
+				let mut has_parent = vec![false; self.nodes.len()];
+				for node in &self.nodes {
+					for &child in &node.children {
+						has_parent[child] = true;
+					}
+				}
+				(0..self.nodes.len()).filter(|&i| !has_parent[i]).collect()
+			}
+		}
+	}
+	/// calculates the world space transformation of every node
+	pub fn world_transforms(&self) -> Vec<Mat4<T>> {
+		let mut transforms = vec![Mat4::identity(); self.nodes.len()];
+		let mut stack: Vec<(usize, Mat4<T>)> = vec![];
+		let mut has_parent = vec![false; self.nodes.len()];
+		for node in &self.nodes {
+			for &child in &node.children {
+				has_parent[child] = true;
+			}
+		}
+		stack.extend((0..self.nodes.len()).filter(|&i| !has_parent[i]).map(|i| (i, Mat4::identity())));
+		while let Some((index, parent)) = stack.pop() {
+			let node = &self.nodes[index];
+			transforms[index] = parent * node.transform.matrix();
+			stack.extend(node.children.iter().map(|&child| (child, transforms[index])));
+		}
+		transforms
+	}
+	/// lists every mesh in the displayed scene together with its world space transformation
+	///
+	/// returns the transformation and the index of the mesh for every node with a mesh
+	pub fn mesh_instances(&self) -> Vec<(Mat4<T>, usize)> {
+		let transforms = self.world_transforms();
+		let mut instances = vec![];
+		let mut stack = self.root_nodes();
+		while let Some(index) = stack.pop() {
+			let node = &self.nodes[index];
+			if let Some(mesh) = node.mesh {
+				instances.push((transforms[index], mesh));
+			}
+			stack.extend(node.children.iter().rev());
+		}
+		instances
+	}
+	/// merges all primitives of the displayed scene into a single world space Mesh
+	///
+	/// Normals and texture coordinates are only kept if all primitives have them
+	pub fn scene_mesh(&self) -> Mesh<T> {
+		let instances = self.mesh_instances();
+		let primitives = || instances.iter().flat_map(|&(m, i)| self.meshes[i].primitives.iter().map(move |p| (m, p)));
+		let keep_normals = primitives().all(|(_, p)| p.mesh.normals.is_some());
+		let keep_uvs = primitives().all(|(_, p)| p.mesh.uvs.is_some());
+		let mut result = Mesh::default();
+		for (matrix, primitive) in primitives() {
+			let mesh = &primitive.mesh;
+			let offset = result.positions.len() as u32;
+			result.positions.extend(mesh.positions.iter().map(|&p| matrix.transform_point(p)));
+			if let (true, Some(normals)) = (keep_normals, &mesh.normals) {
+				let normal_matrix = matrix.normal_matrix();
+				let transformed = normals.iter().map(|&n| (normal_matrix * n).norm());
+				result.normals.get_or_insert_with(Vec::new).extend(transformed);
+			}
+			if let (true, Some(uvs)) = (keep_uvs, &mesh.uvs) {
+				result.uvs.get_or_insert_with(Vec::new).extend_from_slice(uvs);
+			}
+			// a Matrix that mirrors the Mesh also flips the winding order
+			let mirrored = matrix.determinant() < T::ZERO;
+			result.faces.extend(mesh.faces.iter().map(|f| {
+				if mirrored {
+					[f[0] + offset, f[2] + offset, f[1] + offset]
+				} else {
+					[f[0] + offset, f[1] + offset, f[2] + offset]
+				}
+			}));
+		}
+		result
+	}
+}
+
+/// splits a `.glb` file into its JSON and binary chunk
+fn split_glb(bytes: &[u8]) -> Result<(&[u8], Option<&[u8]>), Error> {
+	let u32_at = |offset: usize| -> Result<u32, Error> {
+		bytes
+			.get(offset..offset + 4)
+			.map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+			.ok_or_else(|| Error::Invalid("unexpected end of GLB file".to_string()))
+	};
+	if u32_at(4)? != 2 {
+		return Err(Error::Invalid(format!("unsupported GLB version {}", u32_at(4)?)));
+	}
+	let length = (u32_at(8)? as usize).min(bytes.len());
+	let mut offset = 12;
+	let mut json = None;
+	let mut binary = None;
+	while offset + 8 <= length {
+		let chunk_length = u32_at(offset)? as usize;
+		let chunk_type = u32_at(offset + 4)?;
+		let data = bytes
+			.get(offset + 8..offset + 8 + chunk_length)
+			.ok_or_else(|| Error::Invalid("unexpected end of GLB file".to_string()))?;
+		match chunk_type {
+			CHUNK_JSON if json.is_none() => json = Some(data),
+			CHUNK_BIN if binary.is_none() => binary = Some(data),
+			_ => {}
+		}
+		offset += 8 + chunk_length;
+	}
+	let json = json.ok_or_else(|| Error::Invalid("GLB file has no JSON chunk".to_string()))?;
+	Ok((json, binary))
+}
+
+/// loads the content of a buffer
+fn load_buffer(index: usize, buffer: &Json, binary: Option<&[u8]>, directory: Option<&Path>) -> Result<Vec<u8>, Error> {
+	let length = buffer
+		.get("byteLength")
+		.and_then(Json::as_usize)
+		.ok_or_else(|| Error::Invalid(format!("buffer {} has no byteLength", index)))?;
+	let data = match buffer.get("uri").and_then(Json::as_str) {
+		Some(uri) if uri.starts_with("data:") => {
+			let encoded = uri
+				.find(";base64,")
+				.map(|start| &uri[start + ";base64,".len()..])
+				.ok_or_else(|| Error::Invalid(format!("data URI of buffer {} is not base64 encoded", index)))?;
+			decode_base64(encoded).ok_or_else(|| Error::Invalid(format!("invalid base64 in buffer {}", index)))?
+		}
+		Some(uri) => {
+			let directory = directory.ok_or_else(|| Error::Invalid(format!("external buffer `{}` can only be loaded from a file", uri)))?;
+			let path = relative_path(uri).ok_or_else(|| Error::Invalid(format!("external buffer `{}` is not inside the directory of the file", uri)))?;
+			let mut data = vec![];
+			File::open(directory.join(path))?.read_to_end(&mut data)?;
+			data
+		}
+		None if index == 0 => binary
+			.ok_or_else(|| Error::Invalid("buffer 0 has no uri and there is no binary chunk".to_string()))?
+			.to_vec(),
+		None => return Err(Error::Invalid(format!("buffer {} has no uri", index))),
+	};
+	if data.len() < length {
+		return Err(Error::Invalid(format!("buffer {} is shorter than its byteLength", index)));
+	}
+	Ok(data)
+}
+
+/// percent-decodes a relative URI into a path that can't leave the directory it is relative to
+///
+/// returns None for absolute paths, paths that escape through `..` and invalid escapes
+fn relative_path(uri: &str) -> Option<PathBuf> {
+	let mut bytes = Vec::with_capacity(uri.len());
+	let mut rest = uri.as_bytes();
+	while let Some((&c, tail)) = rest.split_first() {
+		if c == b'%' {
+			let digits = ::std::str::from_utf8(tail.get(..2)?).ok()?;
+			bytes.push(u8::from_str_radix(digits, 16).ok()?);
+			rest = &tail[2..];
+		} else {
+			bytes.push(c);
+			rest = tail;
+		}
+	}
+	let path = PathBuf::from(String::from_utf8(bytes).ok()?);
+	let mut depth = 0usize;
+	for component in path.components() {
+		depth = match component {
+			Component::Normal(_) => depth + 1,
+			Component::CurDir => depth,
+			Component::ParentDir => depth.checked_sub(1)?,
+			Component::RootDir | Component::Prefix(_) => return None,
+		};
+	}
+	Some(path)
+}
+
+/// decodes standard or URL-safe base64, with or without padding
+fn decode_base64(text: &str) -> Option<Vec<u8>> {
+	let mut result = Vec::with_capacity(text.len() * 3 / 4);
+	let mut accumulator = 0u32;
+	let mut bits = 0;
+	for c in text.bytes().take_while(|&c| c != b'=') {
+		let value = match c {
+			b'A'..=b'Z' => c - b'A',
+			b'a'..=b'z' => c - b'a' + 26,
+			b'0'..=b'9' => c - b'0' + 52,
+			b'+' | b'-' => 62,
+			b'/' | b'_' => 63,
+			_ => return None,
+		};
+		accumulator = (accumulator << 6) | value as u32;
+		bits += 6;
+		if bits >= 8 {
+			bits -= 8;
+			result.push((accumulator >> bits) as u8);
+		}
+	}
+	Some(result)
+}
+
+/// Returns the array with the given key, or an empty slice if there is none
+fn array<'a>(json: &'a Json, key: &str) -> &'a [Json] {
+	json.get(key).and_then(Json::as_array).unwrap_or(&[])
+}
+
+/// Returns the string with the given key, or an empty String if there is none
+fn string(json: &Json, key: &str) -> String {
+	json.get(key).and_then(Json::as_str).unwrap_or("").to_string()
+}
+
+/// Returns the list of indices with the given key
+fn indices(json: &Json, key: &str) -> Vec<usize> {
+	array(json, key).iter().filter_map(Json::as_usize).collect()
+}
+
+/// Returns the list of numbers with the given key, if it has exactly `count` entries that are all numbers
+fn numbers<T: Scalar>(json: &Json, key: &str, count: usize) -> Option<Vec<T>> {
+	let values: Vec<T> = array(json, key).iter().map(|v| v.as_f64().map(T::from_f64)).collect::<Option<_>>()?;
+	if values.len() == count {
+		Some(values)
+	} else {
+		None
+	}
+}
+
+fn parse_node<T: Scalar>(index: usize, node: &Json) -> Result<GltfNode<T>, Error> {
+	// a missing property is None, but a property that is present must have the right length
+	let property = |key: &str, count: usize| -> Result<Option<Vec<T>>, Error> {
+		match node.get(key) {
+			Some(_) => numbers(node, key, count)
+				.map(Some)
+				.ok_or_else(|| Error::Invalid(format!("{} of node {} needs {} numbers", key, index, count))),
+			None => Ok(None),
+		}
+	};
+	let transform = if let Some(m) = property("matrix", 16)? {
+		// the Matrix is stored in column-major order
+		let mut matrix = Mat4::new();
+		for col in 0..4 {
+			for row in 0..4 {
+				matrix[row][col] = m[col * 4 + row];
+			}
+		}
+		NodeTransform::Matrix(matrix)
+	} else {
+		let translation = property("translation", 3)?.map_or(Vec3::new(), |t| Vec3::from((t[0], t[1], t[2])));
+		let rotation = property("rotation", 4)?.map_or(Quat::identity(), |r| Quat::from_parts(Vec3::from((r[0], r[1], r[2])), r[3]));
+		let scale = property("scale", 3)?.map_or(Vec3::from((T::ONE, T::ONE, T::ONE)), |s| Vec3::from((s[0], s[1], s[2])));
+		NodeTransform::Trs {
+			translation,
+			rotation,
+			scale,
+		}
+	};
+	Ok(GltfNode {
+		name: string(node, "name"),
+		transform,
+		mesh: node.get("mesh").and_then(Json::as_usize),
+		children: indices(node, "children"),
+	})
+}
+
+fn parse_material<T: Scalar>(material: &Json) -> GltfMaterial<T> {
+	let mut result = GltfMaterial {
+		name: string(material, "name"),
+		..Default::default()
+	};
+	if let Some(pbr) = material.get("pbrMetallicRoughness") {
+		if let Some(c) = numbers::<T>(pbr, "baseColorFactor", 4) {
+			result.base_color = Vec4 {
+				x: c[0],
+				y: c[1],
+				z: c[2],
+				w: c[3],
+			};
+		}
+		if let Some(metallic) = pbr.get("metallicFactor").and_then(Json::as_f64) {
+			result.metallic = T::from_f64(metallic);
+		}
+		if let Some(roughness) = pbr.get("roughnessFactor").and_then(Json::as_f64) {
+			result.roughness = T::from_f64(roughness);
+		}
+	}
+	if let Some(e) = numbers::<T>(material, "emissiveFactor", 3) {
+		result.emissive = Vec3::from((e[0], e[1], e[2]));
+	}
+	result.alpha_mode = match material.get("alphaMode").and_then(Json::as_str) {
+		Some("MASK") => AlphaMode::Mask,
+		Some("BLEND") => AlphaMode::Blend,
+		_ => AlphaMode::Opaque,
+	};
+	if let Some(cutoff) = material.get("alphaCutoff").and_then(Json::as_f64) {
+		result.alpha_cutoff = T::from_f64(cutoff);
+	}
+	result.double_sided = material.get("doubleSided").and_then(Json::as_bool).unwrap_or(false);
+	result
+}
+
+/// Reads the data of accessors from the loaded buffers
+struct AccessorReader<'a> {
+	root: &'a Json,
+	buffers: Vec<Vec<u8>>,
+	/// the size of the JSON and all buffers, which limits accessors that are initialized with zeros
+	input_length: usize,
+}
+
+impl<'a> AccessorReader<'a> {
+	/// reads the accessor at `index`, returning all values as a flat list of numbers
+	///
+	/// Normalized integers are converted to `[0, 1]` or `[-1, 1]`.
+	fn read(&self, index: usize, expected_type: &[&str]) -> Result<(Vec<f64>, usize), Error> {
+		let invalid = |message: &str| Error::Invalid(format!("accessor {}: {}", index, message));
+		let accessor = array(self.root, "accessors").get(index).ok_or_else(|| invalid("does not exist"))?;
+		let count = accessor.get("count").and_then(Json::as_usize).ok_or_else(|| invalid("has no count"))?;
+		let kind = accessor.get("type").and_then(Json::as_str).unwrap_or("");
+		if !expected_type.contains(&kind) {
+			return Err(invalid(&format!("expected type {:?}, found `{}`", expected_type, kind)));
+		}
+		let components = match kind {
+			"SCALAR" => 1,
+			"VEC2" => 2,
+			"VEC3" => 3,
+			"VEC4" => 4,
+			_ => return Err(invalid(&format!("unsupported type `{}`", kind))),
+		};
+		if accessor.get("sparse").is_some() {
+			return Err(invalid("sparse accessors are not supported"));
+		}
+		let component_type = accessor.get("componentType").and_then(Json::as_usize).unwrap_or(0);
+		let (size, max): (usize, f64) = match component_type {
+			5120 => (1, 127.0),
+			5121 => (1, 255.0),
+			5122 => (2, 32767.0),
+			5123 => (2, 65535.0),
+			5125 => (4, 0.0),
+			5126 => (4, 0.0),
+			_ => return Err(invalid(&format!("unsupported componentType {}", component_type))),
+		};
+		let normalized = accessor.get("normalized").and_then(Json::as_bool).unwrap_or(false) && max > 0.0;
+		let element_size = size * components;
+		let byte_length = count.checked_mul(element_size).ok_or_else(|| invalid("count is too large"))?;
+
+		let view_index = match accessor.get("bufferView").and_then(Json::as_usize) {
+			Some(view) => view,
+			// accessors without a buffer view are initialized with zeros, but can't be larger than the file
+			None if byte_length > self.input_length => return Err(invalid("count is too large")),
+			None => return Ok((vec![0.0; count * components], components)),
+		};
+		let view = array(self.root, "bufferViews").get(view_index).ok_or_else(|| invalid("references a missing bufferView"))?;
+		let buffer = view
+			.get("buffer")
+			.and_then(Json::as_usize)
+			.and_then(|b| self.buffers.get(b))
+			.ok_or_else(|| invalid("references a missing buffer"))?;
+		let view_offset = view.get("byteOffset").and_then(Json::as_usize).unwrap_or(0);
+		let view_length = view.get("byteLength").and_then(Json::as_usize).unwrap_or(0);
+		let data = view_offset
+			.checked_add(view_length)
+			.and_then(|end| buffer.get(view_offset..end))
+			.ok_or_else(|| invalid("bufferView exceeds its buffer"))?;
+		let offset = accessor.get("byteOffset").and_then(Json::as_usize).unwrap_or(0);
+		let stride = view.get("byteStride").and_then(Json::as_usize).unwrap_or(element_size);
+		if stride < element_size {
+			return Err(invalid("byteStride is smaller than an element"));
+		}
+		// the end of the last element, which must be checked before allocating space for all elements
+		let end = match count.checked_sub(1) {
+			Some(last) => last.checked_mul(stride).and_then(|start| start.checked_add(offset)).and_then(|start| start.checked_add(element_size)),
+			None => Some(0),
+		};
+		if end.is_none_or(|end| end > data.len()) {
+			return Err(invalid("exceeds its bufferView"));
+		}
+
+		let mut values = Vec::with_capacity(count * components);
+		for element in 0..count {
+			for component in 0..components {
+				let start = offset + element * stride + component * size;
+				let b = &data[start..start + size];
+				let value = match component_type {
+					5120 => b[0] as i8 as f64,
+					5121 => b[0] as f64,
+					5122 => i16::from_le_bytes([b[0], b[1]]) as f64,
+					5123 => u16::from_le_bytes([b[0], b[1]]) as f64,
+					5125 => u32::from_le_bytes([b[0], b[1], b[2], b[3]]) as f64,
+					_ => f32::from_le_bytes([b[0], b[1], b[2], b[3]]) as f64,
+				};
+				values.push(if normalized { (value / max).max(-1.0) } else { value });
+			}
+		}
+		Ok((values, components))
+	}
+	fn vectors<T: Scalar>(&self, index: usize) -> Result<Vec<Vec3<T>>, Error> {
+		let (values, _) = self.read(index, &["VEC3"])?;
+		Ok(values.chunks(3).map(|v| Vec3::from((T::from_f64(v[0]), T::from_f64(v[1]), T::from_f64(v[2])))).collect())
+	}
+	fn mesh<T: Scalar>(&self, index: usize, mesh: &Json) -> Result<GltfMesh<T>, Error> {
+		let mut primitives = vec![];
+		for primitive in array(mesh, "primitives") {
+			let mode = primitive.get("mode").and_then(Json::as_usize).unwrap_or(4);
+			if mode < 4 {
+				// points and lines have no surface
+				continue;
+			}
+			let attributes = primitive.get("attributes");
+			let attribute = |name: &str| attributes.and_then(|a| a.get(name)).and_then(Json::as_usize);
+			let position = attribute("POSITION").ok_or_else(|| Error::Invalid(format!("a primitive of mesh {} has no POSITION", index)))?;
+			let positions = self.vectors(position)?;
+			let normals = attribute("NORMAL").map(|n| self.vectors(n)).transpose()?;
+			let uvs = match attribute("TEXCOORD_0") {
+				Some(uv) => {
+					let (values, _) = self.read(uv, &["VEC2"])?;
+					Some(values.chunks(2).map(|v| Vec2 { x: T::from_f64(v[0]), y: T::from_f64(v[1]) }).collect())
+				}
+				None => None,
+			};
+			let corners: Vec<u32> = match primitive.get("indices").and_then(Json::as_usize) {
+				Some(indices) => self.read(indices, &["SCALAR"])?.0.into_iter().map(|i| i as u32).collect(),
+				None => (0..positions.len() as u32).collect(),
+			};
+			if corners.iter().any(|&c| c as usize >= positions.len()) {
+				return Err(Error::Invalid(format!("a primitive of mesh {} has invalid indices", index)));
+			}
+			let faces = match mode {
+				4 => corners.chunks(3).filter(|c| c.len() == 3).map(|c| [c[0], c[1], c[2]]).collect(),
+				// every other Triangle of a strip has to be flipped to keep the winding order
+				5 => (2..corners.len())
+					.map(|i| if i % 2 == 0 { [corners[i - 2], corners[i - 1], corners[i]] } else { [corners[i - 1], corners[i - 2], corners[i]] })
+					.collect(),
+				6 => (2..corners.len()).map(|i| [corners[0], corners[i - 1], corners[i]]).collect(),
+				_ => return Err(Error::Invalid(format!("a primitive of mesh {} has unknown mode {}", index, mode))),
+			};
+			let mesh = Mesh {
+				positions,
+				normals,
+				uvs,
+				faces,
+			};
+			if !mesh.is_valid() {
+				return Err(Error::Invalid(format!("the attributes of a primitive of mesh {} have different lengths", index)));
+			}
+			primitives.push(GltfPrimitive {
+				mesh,
+				material: primitive.get("material").and_then(Json::as_usize),
+			});
+		}
+		Ok(GltfMesh {
+			name: string(mesh, "name"),
+			primitives,
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use vector::Vector;
+
+	fn encode_base64(bytes: &[u8]) -> String {
+		const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+		let mut result = String::new();
+		for chunk in bytes.chunks(3) {
+			let n = chunk.iter().enumerate().fold(0u32, |acc, (i, &b)| acc | (b as u32) << (16 - 8 * i));
+			for i in 0..4 {
+				if i <= chunk.len() {
+					result.push(ALPHABET[(n >> (18 - 6 * i) & 63) as usize] as char);
+				} else {
+					result.push('=');
+				}
+			}
+		}
+		result
+	}
+
+	/// a single Triangle with normals and indices
+	fn buffer() -> Vec<u8> {
+		let floats: [f32; 18] = [0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0];
+		let mut bytes: Vec<u8> = floats.iter().flat_map(|f| f.to_le_bytes().to_vec()).collect();
+		bytes.extend_from_slice(&[0, 0, 1, 0, 2, 0, 0, 0]);
+		bytes
+	}
+
+	fn document(uri: &str) -> String {
+		format!(
+			r#"{{
+	"asset": {{ "version": "2.0" }},
+	"scene": 0,
+	"scenes": [{{ "name": "main", "nodes": [0] }}],
+	"nodes": [
+		{{ "name": "parent", "translation": [10, 0, 0], "children": [1] }},
+		{{ "name": "child", "mesh": 0, "matrix": [2,0,0,0, 0,2,0,0, 0,0,2,0, 0,0,5,1] }},
+		{{ "name": "unused", "mesh": 0 }}
+	],
+	"meshes": [{{ "name": "triangle", "primitives": [{{ "attributes": {{ "POSITION": 0, "NORMAL": 1 }}, "indices": 2, "material": 0 }}] }}],
+	"materials": [{{ "name": "red", "pbrMetallicRoughness": {{ "baseColorFactor": [1, 0, 0, 1], "metallicFactor": 0.25 }}, "doubleSided": true }}],
+	"buffers": [{{ {} "byteLength": 80 }}],
+	"bufferViews": [
+		{{ "buffer": 0, "byteOffset": 0, "byteLength": 72 }},
+		{{ "buffer": 0, "byteOffset": 72, "byteLength": 6 }}
+	],
+	"accessors": [
+		{{ "bufferView": 0, "componentType": 5126, "count": 3, "type": "VEC3" }},
+		{{ "bufferView": 0, "byteOffset": 36, "componentType": 5126, "count": 3, "type": "VEC3" }},
+		{{ "bufferView": 1, "componentType": 5123, "count": 3, "type": "SCALAR" }}
+	]
+}}"#,
+			uri
+		)
+	}
+
+	fn check(gltf: &Gltf) {
+		assert_eq!(gltf.nodes.len(), 3);
+		assert_eq!(gltf.nodes[0].children, vec![1]);
+		assert_eq!(gltf.root_nodes(), vec![0]);
+		let primitive = &gltf.meshes[0].primitives[0];
+		assert_eq!(primitive.material, Some(0));
+		assert_eq!(primitive.mesh.faces, vec![[0, 1, 2]]);
+		assert_eq!(primitive.mesh.positions[1], Vector::from((1.0, 0.0, 0.0)));
+		assert_eq!(primitive.mesh.normals.as_ref().unwrap()[2], Vector::from((0.0, 0.0, 1.0)));
+
+		let material = &gltf.materials[0];
+		assert_eq!(material.name, "red");
+		assert_eq!(material.base_color, Vec4 { x: 1.0, y: 0.0, z: 0.0, w: 1.0 });
+		assert!((material.metallic - 0.25).abs() <= f32::EPSILON);
+		assert!((material.roughness - 1.0).abs() <= f32::EPSILON);
+		assert!(material.double_sided);
+
+		let instances = gltf.mesh_instances();
+		assert_eq!(instances.len(), 1);
+		assert_eq!(instances[0].0.transform_point(Vector::from((1.0, 0.0, 0.0))), Vector::from((12.0, 0.0, 5.0)));
+		let mesh = gltf.scene_mesh();
+		assert_eq!(mesh.positions, vec![Vector::from((10.0, 0.0, 5.0)), Vector::from((12.0, 0.0, 5.0)), Vector::from((10.0, 2.0, 5.0))]);
+		assert_eq!(mesh.normals.as_ref().unwrap()[0], Vector::from((0.0, 0.0, 1.0)));
+	}
+
+	#[test]
+	fn gltf_embedded() {
+		let uri = format!(r#""uri": "data:application/octet-stream;base64,{}","#, encode_base64(&buffer()));
+		let gltf: Gltf = Gltf::parse(document(&uri).as_bytes(), None).unwrap();
+		check(&gltf);
+		assert_eq!(decode_base64("aGVsbG8="), Some(b"hello".to_vec()));
+		assert_eq!(decode_base64("aGVsbG8"), Some(b"hello".to_vec()));
+		assert_eq!(decode_base64("a*"), None);
+	}
+
+	#[test]
+	fn gltf_external() {
+		let directory = ::std::env::temp_dir().join(format!("utils_3d_gltf_{}", ::std::process::id()));
+		let models = directory.join("models");
+		::std::fs::create_dir_all(&models).unwrap();
+		::std::fs::write(models.join("Box Data.bin"), buffer()).unwrap();
+		::std::fs::write(directory.join("outside.bin"), buffer()).unwrap();
+		let parse = |uri: &str| Gltf::<f32>::parse(document(&format!(r#""uri": "{}","#, uri)).as_bytes(), Some(&models));
+		check(&parse("Box%20Data.bin").unwrap());
+		check(&parse("./Box%20Data.bin").unwrap());
+		// buffers outside of the directory are rejected, even if they exist
+		for uri in &["../outside.bin", "%2E%2E/outside.bin", "./../outside.bin", "/etc/passwd", "Box%2"] {
+			match parse(uri) {
+				Err(Error::Invalid(_)) => {}
+				other => panic!("expected {} to be rejected, got {:?}", uri, other),
+			}
+		}
+		::std::fs::remove_dir_all(&directory).unwrap();
+		assert_eq!(relative_path("a/../b%C3%A4"), Some(PathBuf::from("a/../bä")));
+		assert_eq!(relative_path("a/../../b"), None);
+		assert_eq!(relative_path("%FF"), None);
+	}
+
+	#[test]
+	fn gltf_binary() {
+		let mut json = document("").into_bytes();
+		while !json.len().is_multiple_of(4) {
+			json.push(b' ');
+		}
+		let bin = buffer();
+		let mut glb = b"glTF".to_vec();
+		glb.extend_from_slice(&2u32.to_le_bytes());
+		glb.extend_from_slice(&((12 + 8 + json.len() + 8 + bin.len()) as u32).to_le_bytes());
+		glb.extend_from_slice(&(json.len() as u32).to_le_bytes());
+		glb.extend_from_slice(&CHUNK_JSON.to_le_bytes());
+		glb.extend_from_slice(&json);
+		glb.extend_from_slice(&(bin.len() as u32).to_le_bytes());
+		glb.extend_from_slice(&CHUNK_BIN.to_le_bytes());
+		glb.extend_from_slice(&bin);
+		check(&Gltf::parse(&glb, None).unwrap());
+
+		glb.truncate(glb.len() - 10);
+		assert!(Gltf::<f32>::parse(&glb, None).is_err());
+	}
+
+	#[test]
+	fn gltf_errors() {
+		let parse = |source: &str| Gltf::<f32>::parse(source.as_bytes(), None);
+		assert!(parse(r#"{ "asset": { "version": "1.0" } }"#).is_err());
+		assert!(parse(&document(r#""uri": "external.bin","#)).is_err());
+		let cycle = r#"{ "asset": { "version": "2.0" }, "nodes": [{ "children": [1] }, { "children": [0] }] }"#;
+		assert!(parse(cycle).is_err());
+		match parse("{ \"asset\": \n [") {
+			Err(Error::Parse { line, .. }) => assert_eq!(line, 2),
+			other => panic!("expected a parse error, got {:?}", other),
+		}
+
+		let empty = parse(r#"{ "asset": { "version": "2.0" }, "nodes": [{ "translation": [1, 2, 3] }, {}] }"#).unwrap();
+		assert_eq!(empty.root_nodes(), vec![0, 1]);
+		assert_eq!(empty.world_transforms()[0].transform_point(Vector::new()), Vector::from((1.0, 2.0, 3.0)));
+		assert!(empty.scene_mesh().faces.is_empty());
+		// malformed transformations are errors instead of falling back to the identity
+		for transform in &[r#""translation": [1, 2]"#, r#""rotation": [0, 0, 0, "1"]"#, r#""scale": 2"#, r#""matrix": [1, 0, 0, 1]"#] {
+			assert!(parse(&format!(r#"{{ "asset": {{ "version": "2.0" }}, "nodes": [{{ {} }}] }}"#, transform)).is_err());
+		}
+
+		// huge counts are rejected before anything is allocated
+		let huge = |count: &str, view: &str| {
+			format!(
+				r#"{{ "asset": {{ "version": "2.0" }},
+	"meshes": [{{ "primitives": [{{ "attributes": {{ "POSITION": 0 }} }}] }}],
+	"accessors": [{{ {} "componentType": 5126, "count": {}, "type": "VEC3" }}],
+	"buffers": [{{ "uri": "data:application/octet-stream;base64,AAAAAAAAAAAAAAAA", "byteLength": 12 }}],
+	"bufferViews": [{{ "buffer": 0, "byteLength": 12 }}] }}"#,
+				view, count
+			)
+		};
+		assert!(parse(&huge("1", r#""bufferView": 0,"#)).is_ok());
+		for count in &["1e19", "1e15", "2"] {
+			assert!(parse(&huge(count, r#""bufferView": 0,"#)).is_err());
+		}
+		assert!(parse(&huge("1e19", "")).is_err());
+		assert!(parse(&huge("1e12", "")).is_err());
+		assert!(parse(&huge("3", "")).is_ok());
+	}
+}
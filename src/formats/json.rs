@@ -0,0 +1,258 @@
+//! A minimal JSON parser, just enough for reading the structure of glTF files
+
+use formats::Error;
+
+/// A parsed JSON value
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum Json {
+	Null,
+	Bool(bool),
+	Number(f64),
+	String(String),
+	Array(Vec<Json>),
+	/// The members of an object in the order they appear in
+	Object(Vec<(String, Json)>),
+}
+
+impl Json {
+	/// parses a complete JSON document
+	pub fn parse(source: &str) -> Result<Json, Error> {
+		let mut parser = Parser {
+			source: source.as_bytes(),
+			position: 0,
+		};
+		parser.skip_whitespace();
+		let value = parser.value(0)?;
+		parser.skip_whitespace();
+		if parser.position != parser.source.len() {
+			return Err(parser.error("unexpected characters after the end of the document"));
+		}
+		Ok(value)
+	}
+	/// Returns the member with the given key if this is an object
+	pub fn get(&self, key: &str) -> Option<&Json> {
+		match *self {
+			Json::Object(ref members) => members.iter().find(|m| m.0 == key).map(|m| &m.1),
+			_ => None,
+		}
+	}
+	pub fn as_f64(&self) -> Option<f64> {
+		match *self {
+			Json::Number(n) => Some(n),
+			_ => None,
+		}
+	}
+	/// Returns the value if it is a non-negative integer
+	pub fn as_usize(&self) -> Option<usize> {
+		match *self {
+			Json::Number(n) if n >= 0.0 && n.fract() == 0.0 => Some(n as usize),
+			_ => None,
+		}
+	}
+	pub fn as_bool(&self) -> Option<bool> {
+		match *self {
+			Json::Bool(b) => Some(b),
+			_ => None,
+		}
+	}
+	pub fn as_str(&self) -> Option<&str> {
+		match *self {
+			Json::String(ref s) => Some(s),
+			_ => None,
+		}
+	}
+	pub fn as_array(&self) -> Option<&[Json]> {
+		match *self {
+			Json::Array(ref values) => Some(values),
+			_ => None,
+		}
+	}
+}
+
+/// The maximum nesting of arrays and objects, to avoid overflowing the stack
+const MAX_DEPTH: usize = 128;
+
+struct Parser<'a> {
+	source: &'a [u8],
+	position: usize,
+}
+
+impl<'a> Parser<'a> {
+	fn error(&self, message: &str) -> Error {
+		let line = self.source[..self.position.min(self.source.len())].iter().filter(|&&b| b == b'\n').count() + 1;
+		Error::parse(line, message)
+	}
+	fn peek(&self) -> Option<u8> {
+		self.source.get(self.position).cloned()
+	}
+	fn skip_whitespace(&mut self) {
+		while let Some(b' ') | Some(b'\t') | Some(b'\n') | Some(b'\r') = self.peek() {
+			self.position += 1;
+		}
+	}
+	fn expect(&mut self, text: &str) -> Result<(), Error> {
+		if self.source[self.position..].starts_with(text.as_bytes()) {
+			self.position += text.len();
+			Ok(())
+		} else {
+			Err(self.error(&format!("expected `{}`", text)))
+		}
+	}
+	fn value(&mut self, depth: usize) -> Result<Json, Error> {
+		if depth > MAX_DEPTH {
+			return Err(self.error("too deeply nested"));
+		}
+		match self.peek() {
+			Some(b'n') => self.expect("null").map(|_| Json::Null),
+			Some(b't') => self.expect("true").map(|_| Json::Bool(true)),
+			Some(b'f') => self.expect("false").map(|_| Json::Bool(false)),
+			Some(b'"') => self.string().map(Json::String),
+			Some(b'[') => {
+				self.position += 1;
+				let mut values = vec![];
+				self.skip_whitespace();
+				if self.peek() == Some(b']') {
+					self.position += 1;
+					return Ok(Json::Array(values));
+				}
+				loop {
+					self.skip_whitespace();
+					values.push(self.value(depth + 1)?);
+					self.skip_whitespace();
+					match self.peek() {
+						Some(b',') => self.position += 1,
+						Some(b']') => {
+							self.position += 1;
+							return Ok(Json::Array(values));
+						}
+						_ => return Err(self.error("expected `,` or `]`")),
+					}
+				}
+			}
+			Some(b'{') => {
+				self.position += 1;
+				let mut members = vec![];
+				self.skip_whitespace();
+				if self.peek() == Some(b'}') {
+					self.position += 1;
+					return Ok(Json::Object(members));
+				}
+				loop {
+					self.skip_whitespace();
+					if self.peek() != Some(b'"') {
+						return Err(self.error("expected a key"));
+					}
+					let key = self.string()?;
+					self.skip_whitespace();
+					self.expect(":")?;
+					self.skip_whitespace();
+					members.push((key, self.value(depth + 1)?));
+					self.skip_whitespace();
+					match self.peek() {
+						Some(b',') => self.position += 1,
+						Some(b'}') => {
+							self.position += 1;
+							return Ok(Json::Object(members));
+						}
+						_ => return Err(self.error("expected `,` or `}`")),
+					}
+				}
+			}
+			Some(b'-') | Some(b'0'..=b'9') => self.number(),
+			_ => Err(self.error("expected a value")),
+		}
+	}
+	fn number(&mut self) -> Result<Json, Error> {
+		let start = self.position;
+		while let Some(b'-') | Some(b'+') | Some(b'.') | Some(b'e') | Some(b'E') | Some(b'0'..=b'9') = self.peek() {
+			self.position += 1;
+		}
+		let text = ::std::str::from_utf8(&self.source[start..self.position]).expect("only ASCII characters were consumed");
+		text.parse().map(Json::Number).map_err(|_| self.error(&format!("invalid number `{}`", text)))
+	}
+	fn string(&mut self) -> Result<String, Error> {
+		self.position += 1; // opening quote
+		let mut bytes = vec![];
+		loop {
+			let byte = self.peek().ok_or_else(|| self.error("unterminated string"))?;
+			self.position += 1;
+			match byte {
+				b'"' => break,
+				b'\\' => {
+					let escape = self.peek().ok_or_else(|| self.error("unterminated string"))?;
+					self.position += 1;
+					let c = match escape {
+						b'"' => '"',
+						b'\\' => '\\',
+						b'/' => '/',
+						b'b' => '\u{8}',
+						b'f' => '\u{c}',
+						b'n' => '\n',
+						b'r' => '\r',
+						b't' => '\t',
+						b'u' => self.unicode_escape()?,
+						_ => return Err(self.error("invalid escape sequence")),
+					};
+					let mut buffer = [0; 4];
+					bytes.extend_from_slice(c.encode_utf8(&mut buffer).as_bytes());
+				}
+				_ => bytes.push(byte),
+			}
+		}
+		String::from_utf8(bytes).map_err(|_| self.error("string is not valid UTF-8"))
+	}
+	/// parses the hex digits after `\u`, including a second escape for surrogate pairs
+	fn unicode_escape(&mut self) -> Result<char, Error> {
+		let first = self.hex_digits()?;
+		let code = if (0xD800..0xDC00).contains(&first) {
+			self.expect("\\u")?;
+			let second = self.hex_digits()?;
+			if !(0xDC00..0xE000).contains(&second) {
+				return Err(self.error("invalid unicode escape"));
+			}
+			0x10000 + ((first - 0xD800) << 10) + (second - 0xDC00)
+		} else {
+			first
+		};
+		// a lone low surrogate is not a valid char either
+		::std::char::from_u32(code).ok_or_else(|| self.error("invalid unicode escape"))
+	}
+	/// parses exactly four hex digits
+	fn hex_digits(&mut self) -> Result<u32, Error> {
+		let digits = self.source.get(self.position..self.position + 4).ok_or_else(|| self.error("invalid unicode escape"))?;
+		let digits = ::std::str::from_utf8(digits).map_err(|_| self.error("invalid unicode escape"))?;
+		let value = u32::from_str_radix(digits, 16).map_err(|_| self.error("invalid unicode escape"))?;
+		self.position += 4;
+		Ok(value)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn json_parse() {
+		let json = Json::parse(r#"{ "a": [1, -2.5e2, true, null], "b": { "c": "x\"é😀" }, "d": [] }"#).unwrap();
+		assert_eq!(json.get("a").unwrap().as_array().unwrap()[1].as_f64(), Some(-250.0));
+		assert_eq!(json.get("a").unwrap().as_array().unwrap()[2].as_bool(), Some(true));
+		assert_eq!(json.get("b").unwrap().get("c").unwrap().as_str(), Some("x\"é😀"));
+		assert_eq!(json.get("d").unwrap().as_array().unwrap().len(), 0);
+		assert_eq!(json.get("e"), None);
+		assert_eq!(Json::parse(r#""\u00e9\ud83d\ude00\n""#).unwrap().as_str(), Some("é😀\n"));
+		assert_eq!(Json::Number(3.0).as_usize(), Some(3));
+		assert_eq!(Json::Number(3.5).as_usize(), None);
+
+		let error = |source: &str| match Json::parse(source) {
+			Err(Error::Parse { line, message }) => (line, message),
+			other => panic!("expected an error, got {:?}", other),
+		};
+		assert_eq!(error("{\n\"a\": [1,\n2,,]}"), (3, "expected a value".to_string()));
+		assert_eq!(error("[1] 2"), (1, "unexpected characters after the end of the document".to_string()));
+		assert_eq!(error("\"abc"), (1, "unterminated string".to_string()));
+		assert_eq!(error("{\"a\" 1}"), (1, "expected `:`".to_string()));
+		assert_eq!(error("\"\\u12g4\""), (1, "invalid unicode escape".to_string()));
+		assert_eq!(error("\"\\ud83d\\u0041\""), (1, "invalid unicode escape".to_string()));
+		assert_eq!(error("\"\\ude00\""), (1, "invalid unicode escape".to_string()));
+	}
+}
@@ -0,0 +1,63 @@
+//! A module for reading and writing common 3D file formats
+//!
+//! All readers and writers are implemented without any dependencies.
+
+use std::fmt::{self, Display};
+use std::io;
+
+pub mod gltf;
+mod json;
+pub mod obj;
+pub mod ply;
+pub mod stl;
+
+/// The Error that occurs when reading a file fails
+#[derive(Debug)]
+pub enum Error {
+	/// The underlying reader failed
+	Io(io::Error),
+	/// A line of a text based file could not be parsed
+	Parse {
+		/// The number of the line, starting at 1
+		line: usize,
+		/// A description of the problem
+		message: String,
+	},
+	/// The content of the file is invalid, independent of any line
+	Invalid(String),
+}
+
+impl Error {
+	/// creates a new Parse Error
+	pub(crate) fn parse<S: Into<String>>(line: usize, message: S) -> Error {
+		Error::Parse {
+			line,
+			message: message.into(),
+		}
+	}
+}
+
+impl Display for Error {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match *self {
+			Error::Io(ref err) => write!(f, "{}", err),
+			Error::Parse { line, ref message } => write!(f, "line {}: {}", line, message),
+			Error::Invalid(ref message) => write!(f, "{}", message),
+		}
+	}
+}
+
+impl std::error::Error for Error {
+	fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+		match *self {
+			Error::Io(ref err) => Some(err),
+			_ => None,
+		}
+	}
+}
+
+impl From<io::Error> for Error {
+	fn from(err: io::Error) -> Error {
+		Error::Io(err)
+	}
+}
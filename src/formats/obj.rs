@@ -0,0 +1,438 @@
+//! Reading and writing of [Wavefront OBJ](https://en.wikipedia.org/wiki/Wavefront_.obj_file) files
+//!
+//! Only the geometry is supported: vertex positions (`v`), texture coordinates (`vt`), normals
+//! (`vn`), faces (`f`) and their organization into objects (`o`) and groups (`g`). Polygons with
+//! more than three corners are split into Triangles. All other statements, like materials, are ignored.
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+use formats::Error;
+use shapes::{Mesh, Triangle};
+use vector::Vec3;
+use vector2::Vec2;
+use Scalar;
+
+/// The content of an OBJ file
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Obj<T: Scalar = f32> {
+	/// The groups of faces in the file, in the order they appear in
+	pub groups: Vec<ObjGroup<T>>,
+}
+
+/// A named group of faces in an OBJ file
+///
+/// A new group is started at every `o` and `g` statement. Groups without any faces are skipped.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ObjGroup<T: Scalar = f32> {
+	/// The name of the object (`o`) that the group belongs to, or an empty String if there is none
+	pub object: String,
+	/// The name of the group (`g`), or an empty String if there is none
+	pub name: String,
+	/// The faces of the group with all the vertices that they use
+	pub mesh: Mesh<T>,
+}
+
+impl<T: Scalar> Obj<T> {
+	/// parses the content of an OBJ file
+	pub fn parse(source: &str) -> Result<Obj<T>, Error> {
+		let mut parser = Parser::new();
+		for (i, line) in source.lines().enumerate() {
+			parser.parse_line(i + 1, line)?;
+		}
+		Ok(parser.finish())
+	}
+	/// reads an OBJ file from `reader`
+	pub fn read<R: Read>(reader: R) -> Result<Obj<T>, Error> {
+		let mut parser = Parser::new();
+		for (i, line) in BufReader::new(reader).lines().enumerate() {
+			parser.parse_line(i + 1, &line?)?;
+		}
+		Ok(parser.finish())
+	}
+	/// reads the OBJ file at `path`
+	pub fn load<P: AsRef<Path>>(path: P) -> Result<Obj<T>, Error> {
+		Obj::read(File::open(path)?)
+	}
+	/// writes the groups in the OBJ format to `writer`
+	pub fn write<W: Write>(&self, writer: W) -> io::Result<()> {
+		let mut writer = BufWriter::new(writer);
+		let mut offsets = [0; 3];
+		let mut object = "";
+		// the start of the file and every `o` begin a new group without a name
+		let mut unnamed_group = true;
+		for group in &self.groups {
+			if group.object != object {
+				object = &group.object;
+				writeln!(writer, "o {}", object)?;
+				unnamed_group = true;
+			}
+			if !group.name.is_empty() {
+				writeln!(writer, "g {}", group.name)?;
+			} else if !unnamed_group {
+				writeln!(writer, "g")?;
+			}
+			unnamed_group = false;
+			write_mesh(&mut writer, &group.mesh, &mut offsets)?;
+		}
+		writer.flush()
+	}
+	/// writes the OBJ file to `path`
+	pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+		self.write(File::create(path)?)
+	}
+	/// merges all groups into a single Mesh
+	///
+	/// Normals and texture coordinates are only kept if all groups have them
+	pub fn to_mesh(&self) -> Mesh<T> {
+		let mut mesh = Mesh::default();
+		let keep_normals = self.groups.iter().all(|g| g.mesh.normals.is_some());
+		let keep_uvs = self.groups.iter().all(|g| g.mesh.uvs.is_some());
+		for group in &self.groups {
+			let offset = mesh.positions.len() as u32;
+			mesh.positions.extend_from_slice(&group.mesh.positions);
+			if let (true, Some(normals)) = (keep_normals, &group.mesh.normals) {
+				mesh.normals.get_or_insert_with(Vec::new).extend_from_slice(normals);
+			}
+			if let (true, Some(uvs)) = (keep_uvs, &group.mesh.uvs) {
+				mesh.uvs.get_or_insert_with(Vec::new).extend_from_slice(uvs);
+			}
+			let faces = group.mesh.faces.iter().map(|f| [f[0] + offset, f[1] + offset, f[2] + offset]);
+			mesh.faces.extend(faces);
+		}
+		mesh
+	}
+	/// collects the Triangles of all groups
+	pub fn triangles(&self) -> Vec<Triangle<T>> {
+		self.groups.iter().flat_map(|g| g.mesh.triangles()).collect()
+	}
+}
+
+impl<T: Scalar> From<Mesh<T>> for Obj<T> {
+	fn from(mesh: Mesh<T>) -> Obj<T> {
+		Obj {
+			groups: vec![ObjGroup {
+				mesh,
+				..Default::default()
+			}],
+		}
+	}
+}
+
+/// writes the vertices and faces of a Mesh, where `offsets` are the numbers of positions,
+/// texture coordinates and normals that were written before
+fn write_mesh<T: Scalar, W: Write>(writer: &mut W, mesh: &Mesh<T>, offsets: &mut [usize; 3]) -> io::Result<()> {
+	for p in &mesh.positions {
+		writeln!(writer, "v {} {} {}", p.x, p.y, p.z)?;
+	}
+	for uv in mesh.uvs.iter().flatten() {
+		writeln!(writer, "vt {} {}", uv.x, uv.y)?;
+	}
+	for n in mesh.normals.iter().flatten() {
+		writeln!(writer, "vn {} {} {}", n.x, n.y, n.z)?;
+	}
+	for face in &mesh.faces {
+		write!(writer, "f")?;
+		for &i in face {
+			let i = i as usize + 1;
+			match (mesh.uvs.is_some(), mesh.normals.is_some()) {
+				(false, false) => write!(writer, " {}", i + offsets[0])?,
+				(true, false) => write!(writer, " {}/{}", i + offsets[0], i + offsets[1])?,
+				(false, true) => write!(writer, " {}//{}", i + offsets[0], i + offsets[2])?,
+				(true, true) => write!(writer, " {}/{}/{}", i + offsets[0], i + offsets[1], i + offsets[2])?,
+			}
+		}
+		writeln!(writer)?;
+	}
+	offsets[0] += mesh.positions.len();
+	offsets[1] += mesh.uvs.as_ref().map_or(0, Vec::len);
+	offsets[2] += mesh.normals.as_ref().map_or(0, Vec::len);
+	Ok(())
+}
+
+/// The indices of the position, texture coordinates and normal of a corner of a face
+type Corner = (usize, Option<usize>, Option<usize>);
+
+/// The state of an OBJ file that is being parsed
+struct Parser<T: Scalar> {
+	positions: Vec<Vec3<T>>,
+	uvs: Vec<Vec2<T>>,
+	normals: Vec<Vec3<T>>,
+	groups: Vec<ObjGroup<T>>,
+	current: GroupBuilder,
+}
+
+/// A group whose faces are still being collected
+#[derive(Default)]
+struct GroupBuilder {
+	object: String,
+	name: String,
+	corners: Vec<Corner>,
+	lookup: HashMap<Corner, u32>,
+	faces: Vec<[u32; 3]>,
+}
+
+impl<T: Scalar> Parser<T> {
+	fn new() -> Parser<T> {
+		Parser {
+			positions: vec![],
+			uvs: vec![],
+			normals: vec![],
+			groups: vec![],
+			current: GroupBuilder::default(),
+		}
+	}
+	fn parse_line(&mut self, line_number: usize, line: &str) -> Result<(), Error> {
+		let line = line.split('#').next().unwrap_or("");
+		let mut parts = line.split_whitespace();
+		let keyword = match parts.next() {
+			Some(keyword) => keyword,
+			None => return Ok(()),
+		};
+		let parse_numbers = |parts: ::std::str::SplitWhitespace, min: usize, max: usize| -> Result<Vec<T>, Error> {
+			let numbers = parts
+				.take(max)
+				.map(|s| s.parse::<f64>().map(T::from_f64).map_err(|_| Error::parse(line_number, format!("invalid number `{}`", s))))
+				.collect::<Result<Vec<T>, Error>>()?;
+			if numbers.len() < min {
+				return Err(Error::parse(line_number, format!("`{}` needs at least {} numbers", keyword, min)));
+			}
+			Ok(numbers)
+		};
+		match keyword {
+			"v" => {
+				let v = parse_numbers(parts, 3, 3)?;
+				self.positions.push(Vec3::from((v[0], v[1], v[2])));
+			}
+			"vt" => {
+				let v = parse_numbers(parts, 1, 2)?;
+				self.uvs.push(Vec2 {
+					x: v[0],
+					y: v.get(1).cloned().unwrap_or(T::ZERO),
+				});
+			}
+			"vn" => {
+				let v = parse_numbers(parts, 3, 3)?;
+				self.normals.push(Vec3::from((v[0], v[1], v[2])));
+			}
+			"f" => {
+				let corners = parts
+					.map(|corner| self.parse_corner(line_number, corner))
+					.collect::<Result<Vec<u32>, Error>>()?;
+				if corners.len() < 3 {
+					return Err(Error::parse(line_number, "a face needs at least 3 corners"));
+				}
+				// split the polygon into a fan of Triangles
+				for i in 1..corners.len() - 1 {
+					self.current.faces.push([corners[0], corners[i], corners[i + 1]]);
+				}
+			}
+			"o" => {
+				let object = parts.collect::<Vec<_>>().join(" ");
+				self.start_group(object, String::new());
+			}
+			"g" => {
+				let object = self.current.object.clone();
+				self.start_group(object, parts.collect::<Vec<_>>().join(" "));
+			}
+			_ => {}
+		}
+		Ok(())
+	}
+	/// parses a corner of a face in one of the forms `v`, `v/vt`, `v//vn` or `v/vt/vn`
+	///
+	/// returns the index of the corner in the current group
+	fn parse_corner(&mut self, line_number: usize, corner: &str) -> Result<u32, Error> {
+		let mut parts = corner.split('/');
+		let resolve = |part: Option<&str>, count: usize, name: &str| -> Result<Option<usize>, Error> {
+			let part = match part {
+				Some(part) if !part.is_empty() => part,
+				_ => return Ok(None),
+			};
+			let index: isize = part
+				.parse()
+				.map_err(|_| Error::parse(line_number, format!("invalid index `{}`", part)))?;
+			// positive indices start at 1, negative ones count backwards from the last element
+			let resolved = if index > 0 {
+				index - 1
+			} else {
+				count as isize + index
+			};
+			if index == 0 || resolved < 0 || resolved >= count as isize {
+				return Err(Error::parse(line_number, format!("{} index {} is out of range", name, index)));
+			}
+			Ok(Some(resolved as usize))
+		};
+		let position = resolve(parts.next(), self.positions.len(), "vertex")?
+			.ok_or_else(|| Error::parse(line_number, format!("corner `{}` has no vertex index", corner)))?;
+		let uv = resolve(parts.next(), self.uvs.len(), "texture coordinate")?;
+		let normal = resolve(parts.next(), self.normals.len(), "normal")?;
+		if parts.next().is_some() {
+			return Err(Error::parse(line_number, format!("invalid corner `{}`", corner)));
+		}
+
+		let key = (position, uv, normal);
+		let corners = &mut self.current.corners;
+		Ok(*self.current.lookup.entry(key).or_insert_with(|| {
+			corners.push(key);
+			corners.len() as u32 - 1
+		}))
+	}
+	/// finishes the current group and starts a new one
+	fn start_group(&mut self, object: String, name: String) {
+		let previous = ::std::mem::replace(
+			&mut self.current,
+			GroupBuilder {
+				object,
+				name,
+				..Default::default()
+			},
+		);
+		self.finish_group(previous);
+	}
+	fn finish_group(&mut self, group: GroupBuilder) {
+		if group.faces.is_empty() {
+			return;
+		}
+		let has_uvs = group.corners.iter().any(|c| c.1.is_some());
+		let has_normals = group.corners.iter().any(|c| c.2.is_some());
+		let uvs = &self.uvs;
+		let normals = &self.normals;
+		self.groups.push(ObjGroup {
+			object: group.object,
+			name: group.name,
+			mesh: Mesh {
+				positions: group.corners.iter().map(|c| self.positions[c.0]).collect(),
+				uvs: if has_uvs {
+					Some(group.corners.iter().map(|c| c.1.map_or(Vec2::new(), |i| uvs[i])).collect())
+				} else {
+					None
+				},
+				normals: if has_normals {
+					Some(group.corners.iter().map(|c| c.2.map_or(Vec3::new(), |i| normals[i])).collect())
+				} else {
+					None
+				},
+				faces: group.faces,
+			},
+		});
+	}
+	fn finish(mut self) -> Obj<T> {
+		let last = ::std::mem::take(&mut self.current);
+		self.finish_group(last);
+		Obj { groups: self.groups }
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use vector::Vector;
+
+	const CUBE_SIDES: &str = "
+# two sides of a cube
+mtllib cube.mtl
+o Cube
+v 0 0 0
+v 1 0 0
+v 1 1 0
+v 0 1 0
+v 0 0 1
+v 1 0 1
+vt 0 0
+vt 1 0
+vt 1 1
+vt 0 1
+vn 0 0 -1
+vn 0 -1 0
+g bottom
+usemtl red
+f 1/1/1 4/4/1 3/3/1 2/2/1
+g front
+f -6/1/-1 -5/2/-1 -1/3/-1 -2/4/-1 # relative indices
+";
+
+	#[test]
+	fn obj_parse() {
+		let obj: Obj = Obj::parse(CUBE_SIDES).unwrap();
+		assert_eq!(obj.groups.len(), 2);
+		let bottom = &obj.groups[0];
+		assert_eq!(bottom.object, "Cube");
+		assert_eq!(bottom.name, "bottom");
+		assert_eq!(bottom.mesh.vertex_count(), 4);
+		assert_eq!(bottom.mesh.faces, vec![[0, 1, 2], [0, 2, 3]]);
+		assert_eq!(bottom.mesh.normals.as_ref().unwrap()[0], Vector::from((0.0, 0.0, -1.0)));
+		assert_eq!(bottom.mesh.uvs.as_ref().unwrap()[1], Vec2 { x: 0.0, y: 1.0 });
+		assert_eq!(bottom.mesh.triangle(0).normal(), Vector::from((0.0, 0.0, -1.0)));
+
+		let front = &obj.groups[1];
+		assert_eq!(front.name, "front");
+		assert_eq!(front.mesh.triangle(0).normal(), Vector::from((0.0, -1.0, 0.0)));
+		assert_eq!(front.mesh.positions[2], Vector::from((1.0, 0.0, 1.0)));
+
+		assert_eq!(obj.triangles().len(), 4);
+		let merged = obj.to_mesh();
+		assert_eq!(merged.vertex_count(), 8);
+		assert_eq!(merged.faces[3], [4, 6, 7]);
+		assert!(merged.is_valid());
+		assert!((merged.surface_area() - 2.0).abs() <= 1e-6);
+	}
+
+	#[test]
+	fn obj_errors() {
+		let error = |source: &str| match Obj::<f32>::parse(source) {
+			Err(Error::Parse { line, message }) => (line, message),
+			other => panic!("expected an error, got {:?}", other),
+		};
+		assert_eq!(error("v 1 2 3\nv 1 2 x\n"), (2, "invalid number `x`".to_string()));
+		assert_eq!(error("v 1 2\n"), (1, "`v` needs at least 3 numbers".to_string()));
+		assert_eq!(error("v 1 2 3\n\nf 1 1\n"), (3, "a face needs at least 3 corners".to_string()));
+		assert_eq!(error("v 1 2 3\nf 1 2 1\n"), (2, "vertex index 2 is out of range".to_string()));
+		assert_eq!(error("v 1 2 3\nf 1 0 1\n"), (2, "vertex index 0 is out of range".to_string()));
+		assert_eq!(error("v 1 2 3\nf 1 -2 1\n"), (2, "vertex index -2 is out of range".to_string()));
+		assert_eq!(error("v 1 2 3\nf 1 1/1 1\n"), (2, "texture coordinate index 1 is out of range".to_string()));
+		assert_eq!(error("v 1 2 3\nf 1 1/a 1\n"), (2, "invalid index `a`".to_string()));
+		assert_eq!(format!("{}", Error::parse(4, "oops")), "line 4: oops");
+	}
+
+	#[test]
+	fn obj_roundtrip() {
+		let obj: Obj = Obj::parse(CUBE_SIDES).unwrap();
+		let mut written = vec![];
+		obj.write(&mut written).unwrap();
+		let text = String::from_utf8(written).unwrap();
+		assert!(text.starts_with("o Cube\ng bottom\nv 0 0 0\n"));
+		assert!(text.contains("f 5/5/5 6/6/6 7/7/7\n"));
+		assert_eq!(Obj::read(text.as_bytes()).unwrap(), obj);
+
+		let mut mesh = obj.to_mesh();
+		mesh.uvs = None;
+		mesh.normals = None;
+		let mut written = vec![];
+		Obj::from(mesh.clone()).write(&mut written).unwrap();
+		let text = String::from_utf8(written).unwrap();
+		assert!(text.ends_with("f 5 7 8\n"));
+		assert_eq!(Obj::parse(&text).unwrap().to_mesh(), mesh);
+	}
+
+	#[test]
+	fn obj_roundtrip_unnamed_groups() {
+		let triangle = Mesh::new(vec![Vector::new(), Vector::from((1.0, 0.0, 0.0)), Vector::from((0.0, 1.0, 0.0))], vec![[0, 1, 2]]);
+		let group = |object: &str, name: &str| ObjGroup {
+			object: object.to_string(),
+			name: name.to_string(),
+			mesh: triangle.clone(),
+		};
+		let obj: Obj = Obj {
+			groups: vec![group("", ""), group("", "named"), group("", ""), group("a", ""), group("a", "")],
+		};
+		let mut written = vec![];
+		obj.write(&mut written).unwrap();
+		let text = String::from_utf8(written).unwrap();
+		assert!(text.starts_with("v 0 0 0\n"));
+		assert_eq!(text.lines().filter(|&l| l == "g").count(), 2);
+		assert_eq!(Obj::parse(&text).unwrap(), obj);
+	}
+}
@@ -0,0 +1,808 @@
+//! Reading and writing of [PLY](https://en.wikipedia.org/wiki/PLY_(file_format)) files
+//!
+//! A PLY file consists of a list of elements (usually `vertex` and `face`), each with an arbitrary
+//! list of properties. [Ply](struct.Ply.html) stores all of them, and can be converted to and
+//! from a [PointCloud](../../shapes/struct.PointCloud.html) or a [Mesh](../../shapes/struct.Mesh.html).
+
+use std::fs::File;
+use std::io::{self, BufWriter, Read, Write};
+use std::path::Path;
+
+use formats::Error;
+use shapes::{Mesh, PointCloud};
+use vector::Vec3;
+use vector2::Vec2;
+use Scalar;
+
+/// The encoding of the data in a PLY file
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PlyFormat {
+	/// Human-readable text, one element per line
+	Ascii,
+	/// Binary data with the least significant byte first
+	BinaryLittleEndian,
+	/// Binary data with the most significant byte first
+	BinaryBigEndian,
+}
+
+/// The type of a single number in a PLY file
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PlyType {
+	/// `char` or `int8`
+	Char,
+	/// `uchar` or `uint8`
+	UChar,
+	/// `short` or `int16`
+	Short,
+	/// `ushort` or `uint16`
+	UShort,
+	/// `int` or `int32`
+	Int,
+	/// `uint` or `uint32`
+	UInt,
+	/// `float` or `float32`
+	Float,
+	/// `double` or `float64`
+	Double,
+}
+
+/// The type of a property of a PLY element
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PlyPropertyKind {
+	/// A single number
+	Scalar(PlyType),
+	/// A list of numbers, preceded by their count
+	List {
+		/// The type of the count
+		count: PlyType,
+		/// The type of the numbers in the list
+		item: PlyType,
+	},
+}
+
+/// A named property of a PLY element
+#[derive(Clone, Debug, PartialEq)]
+pub struct PlyProperty {
+	/// The name of the property, like `x` or `vertex_indices`
+	pub name: String,
+	/// The type of the property
+	pub kind: PlyPropertyKind,
+}
+
+/// The value of a property of a single element
+///
+/// All numbers are stored as `f64`, which can represent every PLY type exactly
+#[derive(Clone, Debug, PartialEq)]
+pub enum PlyValue {
+	/// The value of a [scalar property](enum.PlyPropertyKind.html#variant.Scalar)
+	Scalar(f64),
+	/// The values of a [list property](enum.PlyPropertyKind.html#variant.List)
+	List(Vec<f64>),
+}
+
+/// A type of element in a PLY file with all of its instances
+#[derive(Clone, Debug, PartialEq)]
+pub struct PlyElement {
+	/// The name of the element, like `vertex` or `face`
+	pub name: String,
+	/// The properties that every instance of the element has
+	pub properties: Vec<PlyProperty>,
+	/// The instances of the element, with one value per property
+	pub rows: Vec<Vec<PlyValue>>,
+}
+
+/// The content of a PLY file
+#[derive(Clone, Debug, PartialEq)]
+pub struct Ply {
+	/// The encoding that is used when writing the file
+	pub format: PlyFormat,
+	/// The comments in the header
+	pub comments: Vec<String>,
+	/// The elements in the order they are stored in
+	pub elements: Vec<PlyElement>,
+}
+
+impl PlyType {
+	fn parse(name: &str) -> Option<PlyType> {
+		Some(match name {
+			"char" | "int8" => PlyType::Char,
+			"uchar" | "uint8" => PlyType::UChar,
+			"short" | "int16" => PlyType::Short,
+			"ushort" | "uint16" => PlyType::UShort,
+			"int" | "int32" => PlyType::Int,
+			"uint" | "uint32" => PlyType::UInt,
+			"float" | "float32" => PlyType::Float,
+			"double" | "float64" => PlyType::Double,
+			_ => return None,
+		})
+	}
+	fn name(self) -> &'static str {
+		match self {
+			PlyType::Char => "char",
+			PlyType::UChar => "uchar",
+			PlyType::Short => "short",
+			PlyType::UShort => "ushort",
+			PlyType::Int => "int",
+			PlyType::UInt => "uint",
+			PlyType::Float => "float",
+			PlyType::Double => "double",
+		}
+	}
+	/// Returns the number of bytes that a value of this type takes in a binary file
+	fn size(self) -> usize {
+		match self {
+			PlyType::Char | PlyType::UChar => 1,
+			PlyType::Short | PlyType::UShort => 2,
+			PlyType::Int | PlyType::UInt | PlyType::Float => 4,
+			PlyType::Double => 8,
+		}
+	}
+	/// Returns the type that can store all values of the Scalar `T`
+	fn of_scalar<T: Scalar>() -> PlyType {
+		if ::std::mem::size_of::<T>() > 4 {
+			PlyType::Double
+		} else {
+			PlyType::Float
+		}
+	}
+	/// converts the bytes of a binary value, which must be exactly `size()` long
+	fn decode(self, bytes: &[u8], big_endian: bool) -> f64 {
+		let mut buffer = [0u8; 8];
+		buffer[..bytes.len()].copy_from_slice(bytes);
+		if big_endian {
+			buffer[..bytes.len()].reverse();
+		}
+		let [a, b, c, d, ..] = buffer;
+		match self {
+			PlyType::Char => a as i8 as f64,
+			PlyType::UChar => a as f64,
+			PlyType::Short => i16::from_le_bytes([a, b]) as f64,
+			PlyType::UShort => u16::from_le_bytes([a, b]) as f64,
+			PlyType::Int => i32::from_le_bytes([a, b, c, d]) as f64,
+			PlyType::UInt => u32::from_le_bytes([a, b, c, d]) as f64,
+			PlyType::Float => f32::from_le_bytes([a, b, c, d]) as f64,
+			PlyType::Double => f64::from_le_bytes(buffer),
+		}
+	}
+	/// checks if a value can be stored in this type without changing it
+	///
+	/// `Float` only checks the range, since rounding to the closest `f32` is expected
+	fn fits(self, value: f64) -> bool {
+		let (min, max) = match self {
+			PlyType::Char => (i8::MIN as f64, i8::MAX as f64),
+			PlyType::UChar => (0.0, u8::MAX as f64),
+			PlyType::Short => (i16::MIN as f64, i16::MAX as f64),
+			PlyType::UShort => (0.0, u16::MAX as f64),
+			PlyType::Int => (i32::MIN as f64, i32::MAX as f64),
+			PlyType::UInt => (0.0, u32::MAX as f64),
+			PlyType::Float => return !value.is_finite() || value.abs() <= f32::MAX as f64,
+			PlyType::Double => return true,
+		};
+		value.fract() == 0.0 && min <= value && value <= max
+	}
+	/// converts a value to its binary representation
+	///
+	/// The value has to pass `fits`, otherwise it is saturated or truncated
+	fn encode(self, value: f64, big_endian: bool) -> Vec<u8> {
+		let mut bytes = match self {
+			PlyType::Char => vec![value as i8 as u8],
+			PlyType::UChar => vec![value as u8],
+			PlyType::Short => (value as i16).to_le_bytes().to_vec(),
+			PlyType::UShort => (value as u16).to_le_bytes().to_vec(),
+			PlyType::Int => (value as i32).to_le_bytes().to_vec(),
+			PlyType::UInt => (value as u32).to_le_bytes().to_vec(),
+			PlyType::Float => (value as f32).to_le_bytes().to_vec(),
+			PlyType::Double => value.to_le_bytes().to_vec(),
+		};
+		if big_endian {
+			bytes.reverse();
+		}
+		bytes
+	}
+	/// formats a value for an ASCII file
+	fn format(self, value: f64) -> String {
+		match self {
+			// printing the f32 gives the shortest representation that reads back as the same f32
+			PlyType::Float => format!("{}", value as f32),
+			_ => format!("{}", value),
+		}
+	}
+}
+
+impl PlyElement {
+	/// creates a new element with the given properties, but without any instances
+	pub fn new<S: Into<String>>(name: S, properties: Vec<PlyProperty>) -> PlyElement {
+		PlyElement {
+			name: name.into(),
+			properties,
+			rows: vec![],
+		}
+	}
+	/// finds the position of the property with the given name
+	pub fn property_index(&self, name: &str) -> Option<usize> {
+		self.properties.iter().position(|p| p.name == name)
+	}
+	/// collects the values of a scalar property for all instances
+	///
+	/// returns None if there is no scalar property with that name, or if an instance has no value for it
+	pub fn scalars(&self, name: &str) -> Option<Vec<f64>> {
+		let index = self.property_index(name)?;
+		self.rows
+			.iter()
+			.map(|row| match row.get(index)? {
+				&PlyValue::Scalar(value) => Some(value),
+				PlyValue::List(_) => None,
+			})
+			.collect()
+	}
+	/// collects the values of three scalar properties as Vectors
+	fn vectors<T: Scalar>(&self, names: [&str; 3]) -> Option<Vec<Vec3<T>>> {
+		let x = self.scalars(names[0])?;
+		let y = self.scalars(names[1])?;
+		let z = self.scalars(names[2])?;
+		Some((0..x.len()).map(|i| Vec3::from((T::from_f64(x[i]), T::from_f64(y[i]), T::from_f64(z[i])))).collect())
+	}
+}
+
+impl PlyProperty {
+	/// creates a new scalar property
+	pub fn scalar<S: Into<String>>(name: S, kind: PlyType) -> PlyProperty {
+		PlyProperty {
+			name: name.into(),
+			kind: PlyPropertyKind::Scalar(kind),
+		}
+	}
+	/// creates a new list property
+	pub fn list<S: Into<String>>(name: S, count: PlyType, item: PlyType) -> PlyProperty {
+		PlyProperty {
+			name: name.into(),
+			kind: PlyPropertyKind::List { count, item },
+		}
+	}
+}
+
+impl Ply {
+	/// creates an empty PLY file with the given format
+	pub fn new(format: PlyFormat) -> Ply {
+		Ply {
+			format,
+			comments: vec![],
+			elements: vec![],
+		}
+	}
+	/// finds the element with the given name
+	pub fn element(&self, name: &str) -> Option<&PlyElement> {
+		self.elements.iter().find(|e| e.name == name)
+	}
+	/// reads a PLY file from `reader`
+	pub fn read<R: Read>(mut reader: R) -> Result<Ply, Error> {
+		let mut bytes = vec![];
+		reader.read_to_end(&mut bytes)?;
+		Ply::parse(&bytes)
+	}
+	/// reads the PLY file at `path`
+	pub fn load<P: AsRef<Path>>(path: P) -> Result<Ply, Error> {
+		Ply::read(File::open(path)?)
+	}
+	/// parses the content of a PLY file
+	pub fn parse(bytes: &[u8]) -> Result<Ply, Error> {
+		let (mut ply, counts, body_start, body_line) = parse_header(bytes)?;
+		let body = &bytes[body_start..];
+		match ply.format {
+			PlyFormat::Ascii => {
+				let text = ::std::str::from_utf8(body).map_err(|_| Error::Invalid("ASCII PLY data is not valid UTF-8".to_string()))?;
+				parse_ascii_body(&mut ply, &counts, text, body_line)?;
+			}
+			PlyFormat::BinaryLittleEndian => parse_binary_body(&mut ply, &counts, body, false)?,
+			PlyFormat::BinaryBigEndian => parse_binary_body(&mut ply, &counts, body, true)?,
+		}
+		Ok(ply)
+	}
+	/// writes the PLY file in its `format` to `writer`
+	pub fn write<W: Write>(&self, writer: W) -> io::Result<()> {
+		let mut writer = BufWriter::new(writer);
+		writeln!(writer, "ply")?;
+		let format = match self.format {
+			PlyFormat::Ascii => "ascii",
+			PlyFormat::BinaryLittleEndian => "binary_little_endian",
+			PlyFormat::BinaryBigEndian => "binary_big_endian",
+		};
+		writeln!(writer, "format {} 1.0", format)?;
+		for comment in &self.comments {
+			writeln!(writer, "comment {}", comment)?;
+		}
+		for element in &self.elements {
+			writeln!(writer, "element {} {}", element.name, element.rows.len())?;
+			for property in &element.properties {
+				match property.kind {
+					PlyPropertyKind::Scalar(kind) => writeln!(writer, "property {} {}", kind.name(), property.name)?,
+					PlyPropertyKind::List { count, item } => writeln!(writer, "property list {} {} {}", count.name(), item.name(), property.name)?,
+				}
+			}
+		}
+		writeln!(writer, "end_header")?;
+
+		let big_endian = self.format == PlyFormat::BinaryBigEndian;
+		for element in &self.elements {
+			for row in &element.rows {
+				if row.len() != element.properties.len() {
+					let message = format!("instance of element `{}` has {} values for {} properties", element.name, row.len(), element.properties.len());
+					return Err(io::Error::new(io::ErrorKind::InvalidInput, message));
+				}
+				let mut tokens = vec![];
+				for (property, value) in element.properties.iter().zip(row) {
+					match (property.kind, value) {
+						(PlyPropertyKind::Scalar(kind), &PlyValue::Scalar(v)) => tokens.push((kind, v)),
+						(PlyPropertyKind::List { count, item }, PlyValue::List(values)) => {
+							if !count.fits(values.len() as f64) {
+								let message = format!("list of property `{}` is too long for its `{}` count", property.name, count.name());
+								return Err(io::Error::new(io::ErrorKind::InvalidData, message));
+							}
+							tokens.push((count, values.len() as f64));
+							tokens.extend(values.iter().map(|&v| (item, v)));
+						}
+						_ => {
+							let message = format!("value of property `{}` does not match its type", property.name);
+							return Err(io::Error::new(io::ErrorKind::InvalidInput, message));
+						}
+					}
+					if let Some(&(kind, v)) = tokens.iter().find(|&&(kind, v)| !kind.fits(v)) {
+						let message = format!("value {} of property `{}` does not fit into `{}`", v, property.name, kind.name());
+						return Err(io::Error::new(io::ErrorKind::InvalidData, message));
+					}
+				}
+				if self.format == PlyFormat::Ascii {
+					let line: Vec<String> = tokens.iter().map(|&(kind, v)| kind.format(v)).collect();
+					writeln!(writer, "{}", line.join(" "))?;
+				} else {
+					for (kind, v) in tokens {
+						writer.write_all(&kind.encode(v, big_endian))?;
+					}
+				}
+			}
+		}
+		writer.flush()
+	}
+	/// writes the PLY file to `path`
+	pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+		self.write(File::create(path)?)
+	}
+
+	/// converts the `vertex` element to a PointCloud
+	///
+	/// The positions are read from the `x`, `y` and `z` properties. Normals are read from `nx`,
+	/// `ny` and `nz` and colors from `red`, `green`, `blue` and `alpha`, if they exist.
+	pub fn to_point_cloud<T: Scalar>(&self) -> Result<PointCloud<T>, Error> {
+		let vertex = self
+			.element("vertex")
+			.ok_or_else(|| Error::Invalid("PLY file has no `vertex` element".to_string()))?;
+		let positions = vertex
+			.vectors(["x", "y", "z"])
+			.ok_or_else(|| Error::Invalid("`vertex` element needs `x`, `y` and `z` properties".to_string()))?;
+		let normals = vertex.vectors(["nx", "ny", "nz"]);
+		let colors = match (vertex.scalars("red"), vertex.scalars("green"), vertex.scalars("blue")) {
+			(Some(r), Some(g), Some(b)) => {
+				let alpha = vertex.scalars("alpha");
+				// floating point colors are in [0, 1], integer colors in [0, 255]
+				let is_float = |name: &str| {
+					let kind = vertex.property_index(name).map(|i| vertex.properties[i].kind);
+					matches!(kind, Some(PlyPropertyKind::Scalar(PlyType::Float)) | Some(PlyPropertyKind::Scalar(PlyType::Double)))
+				};
+				let channel = |values: &[f64], i: usize, name: &str| {
+					let value = if is_float(name) { values[i] * 255.0 } else { values[i] };
+					value.round().clamp(0.0, 255.0) as u8
+				};
+				Some(
+					(0..r.len())
+						.map(|i| [channel(&r, i, "red"), channel(&g, i, "green"), channel(&b, i, "blue"), alpha.as_ref().map_or(255, |a| channel(a, i, "alpha"))])
+						.collect(),
+				)
+			}
+			_ => None,
+		};
+		Ok(PointCloud {
+			positions,
+			normals,
+			colors,
+		})
+	}
+	/// converts the `vertex` and `face` elements to a Mesh
+	///
+	/// The vertices are read like in [to_point_cloud](#method.to_point_cloud), with texture
+	/// coordinates from `s` and `t` (or `u` and `v`). Faces are read from the `vertex_indices`
+	/// (or `vertex_index`) list of the `face` element, and polygons are split into Triangles.
+	pub fn to_mesh<T: Scalar>(&self) -> Result<Mesh<T>, Error> {
+		let cloud = self.to_point_cloud()?;
+		let vertex = self.element("vertex").expect("checked by to_point_cloud");
+		let uvs = ["s", "u", "texture_u"]
+			.iter()
+			.zip(["t", "v", "texture_v"].iter())
+			.filter_map(|(u, v)| Some((vertex.scalars(u)?, vertex.scalars(v)?)))
+			.next()
+			.map(|(u, v)| (0..u.len()).map(|i| Vec2 { x: T::from_f64(u[i]), y: T::from_f64(v[i]) }).collect());
+
+		let mut faces = vec![];
+		if let Some(face) = self.element("face") {
+			let index = face
+				.property_index("vertex_indices")
+				.or_else(|| face.property_index("vertex_index"))
+				.ok_or_else(|| Error::Invalid("`face` element needs a `vertex_indices` property".to_string()))?;
+			for (i, row) in face.rows.iter().enumerate() {
+				let corners = match row.get(index) {
+					Some(PlyValue::List(corners)) => corners,
+					_ => return Err(Error::Invalid("`vertex_indices` must be a list".to_string())),
+				};
+				if corners.len() < 3 || corners.iter().any(|&c| c < 0.0 || c >= cloud.len() as f64 || c.fract() != 0.0) {
+					return Err(Error::Invalid(format!("face {} has invalid corners", i)));
+				}
+				for j in 1..corners.len() - 1 {
+					faces.push([corners[0] as u32, corners[j] as u32, corners[j + 1] as u32]);
+				}
+			}
+		}
+		Ok(Mesh {
+			positions: cloud.positions,
+			normals: cloud.normals,
+			uvs,
+			faces,
+		})
+	}
+	/// creates a PLY file with a `vertex` element from the PointCloud
+	///
+	/// returns an error if there are not as many normals or colors as positions
+	pub fn from_point_cloud<T: Scalar>(cloud: &PointCloud<T>, format: PlyFormat) -> Result<Ply, Error> {
+		let count = cloud.positions.len();
+		if cloud.normals.as_ref().is_some_and(|n| n.len() != count) || cloud.colors.as_ref().is_some_and(|c| c.len() != count) {
+			return Err(Error::Invalid("PointCloud needs as many normals and colors as positions".to_string()));
+		}
+		let kind = PlyType::of_scalar::<T>();
+		let mut vertex = PlyElement::new("vertex", vec![]);
+		let mut columns: Vec<Vec<f64>> = vec![];
+		let mut add_vectors = |names: [&str; 3], vectors: &[Vec3<T>]| {
+			for (axis, name) in names.iter().enumerate() {
+				vertex.properties.push(PlyProperty::scalar(*name, kind));
+				columns.push(vectors.iter().map(|v| v[axis].to_f64()).collect());
+			}
+		};
+		add_vectors(["x", "y", "z"], &cloud.positions);
+		if let Some(ref normals) = cloud.normals {
+			add_vectors(["nx", "ny", "nz"], normals);
+		}
+		if let Some(ref colors) = cloud.colors {
+			for (channel, name) in ["red", "green", "blue", "alpha"].iter().enumerate() {
+				vertex.properties.push(PlyProperty::scalar(*name, PlyType::UChar));
+				columns.push(colors.iter().map(|c| c[channel] as f64).collect());
+			}
+		}
+		vertex.rows = (0..cloud.len())
+			.map(|i| columns.iter().map(|column| PlyValue::Scalar(column[i])).collect())
+			.collect();
+		Ok(Ply {
+			format,
+			comments: vec![],
+			elements: vec![vertex],
+		})
+	}
+	/// creates a PLY file with a `vertex` and a `face` element from the Mesh
+	///
+	/// returns an error if there are not as many normals or texture coordinates as positions
+	pub fn from_mesh<T: Scalar>(mesh: &Mesh<T>, format: PlyFormat) -> Result<Ply, Error> {
+		if mesh.uvs.as_ref().is_some_and(|uvs| uvs.len() != mesh.positions.len()) {
+			return Err(Error::Invalid("Mesh needs as many texture coordinates as positions".to_string()));
+		}
+		let cloud = PointCloud {
+			positions: mesh.positions.clone(),
+			normals: mesh.normals.clone(),
+			colors: None,
+		};
+		let mut ply = Ply::from_point_cloud(&cloud, format)?;
+		if let Some(ref uvs) = mesh.uvs {
+			let kind = PlyType::of_scalar::<T>();
+			let vertex = &mut ply.elements[0];
+			vertex.properties.push(PlyProperty::scalar("s", kind));
+			vertex.properties.push(PlyProperty::scalar("t", kind));
+			for (row, uv) in vertex.rows.iter_mut().zip(uvs) {
+				row.push(PlyValue::Scalar(uv.x.to_f64()));
+				row.push(PlyValue::Scalar(uv.y.to_f64()));
+			}
+		}
+		let mut face = PlyElement::new("face", vec![PlyProperty::list("vertex_indices", PlyType::UChar, PlyType::UInt)]);
+		face.rows = mesh
+			.faces
+			.iter()
+			.map(|f| vec![PlyValue::List(f.iter().map(|&i| i as f64).collect())])
+			.collect();
+		ply.elements.push(face);
+		Ok(ply)
+	}
+}
+
+/// parses the header of a PLY file
+///
+/// returns the file without data, the number of instances of each element, the position where
+/// the data starts and the number of lines in the header
+fn parse_header(bytes: &[u8]) -> Result<(Ply, Vec<usize>, usize, usize), Error> {
+	let mut ply = Ply::new(PlyFormat::Ascii);
+	let mut counts = vec![];
+	let mut format = None;
+	let mut position = 0;
+	let mut line_number = 0;
+	loop {
+		let end = match bytes[position..].iter().position(|&b| b == b'\n') {
+			Some(end) => position + end,
+			None => return Err(Error::Invalid("PLY header is missing `end_header`".to_string())),
+		};
+		let line = ::std::str::from_utf8(&bytes[position..end]).map_err(|_| Error::parse(line_number + 1, "PLY header is not valid UTF-8"))?;
+		position = end + 1;
+		line_number += 1;
+
+		let mut parts = line.split_whitespace();
+		let keyword = parts.next();
+		if line_number == 1 {
+			if keyword != Some("ply") {
+				return Err(Error::parse(1, "not a PLY file"));
+			}
+			continue;
+		}
+		match keyword {
+			Some("format") => {
+				format = Some(match parts.next() {
+					Some("ascii") => PlyFormat::Ascii,
+					Some("binary_little_endian") => PlyFormat::BinaryLittleEndian,
+					Some("binary_big_endian") => PlyFormat::BinaryBigEndian,
+					other => return Err(Error::parse(line_number, format!("unknown format `{}`", other.unwrap_or("")))),
+				});
+			}
+			Some("comment") => ply.comments.push(line.trim_start()["comment".len()..].trim().to_string()),
+			Some("obj_info") => {}
+			Some("element") => {
+				let (name, count) = match (parts.next(), parts.next().and_then(|c| c.parse().ok())) {
+					(Some(name), Some(count)) => (name, count),
+					_ => return Err(Error::parse(line_number, "`element` needs a name and a count")),
+				};
+				ply.elements.push(PlyElement::new(name, vec![]));
+				counts.push(count);
+			}
+			Some("property") => {
+				let parts: Vec<&str> = parts.collect();
+				let parse_type = |name: &str| PlyType::parse(name).ok_or_else(|| Error::parse(line_number, format!("unknown type `{}`", name)));
+				let property = match parts[..] {
+					["list", count, item, name] => PlyProperty::list(name, parse_type(count)?, parse_type(item)?),
+					[kind, name] => PlyProperty::scalar(name, parse_type(kind)?),
+					_ => return Err(Error::parse(line_number, "invalid property")),
+				};
+				match ply.elements.last_mut() {
+					Some(element) => element.properties.push(property),
+					None => return Err(Error::parse(line_number, "`property` before any `element`")),
+				}
+			}
+			Some("end_header") => break,
+			None => {}
+			Some(other) => return Err(Error::parse(line_number, format!("unexpected `{}`", other))),
+		}
+	}
+	ply.format = format.ok_or_else(|| Error::Invalid("PLY header is missing `format`".to_string()))?;
+	Ok((ply, counts, position, line_number))
+}
+
+fn parse_ascii_body(ply: &mut Ply, counts: &[usize], text: &str, header_lines: usize) -> Result<(), Error> {
+	let mut lines = text.lines().enumerate().map(|(i, line)| (header_lines + i + 1, line)).filter(|(_, line)| !line.trim().is_empty());
+	for (element, &count) in ply.elements.iter_mut().zip(counts) {
+		for _ in 0..count {
+			let (line_number, line) = lines
+				.next()
+				.ok_or_else(|| Error::Invalid(format!("unexpected end of file in element `{}`", element.name)))?;
+			let mut tokens = line.split_whitespace();
+			let mut next = || -> Result<f64, Error> {
+				let token = tokens.next().ok_or_else(|| Error::parse(line_number, "not enough values"))?;
+				token.parse().map_err(|_| Error::parse(line_number, format!("invalid number `{}`", token)))
+			};
+			let mut row = Vec::with_capacity(element.properties.len());
+			for property in &element.properties {
+				row.push(match property.kind {
+					PlyPropertyKind::Scalar(_) => PlyValue::Scalar(next()?),
+					PlyPropertyKind::List { .. } => {
+						let count = next()? as usize;
+						PlyValue::List((0..count).map(|_| next()).collect::<Result<_, _>>()?)
+					}
+				});
+			}
+			if tokens.next().is_some() {
+				return Err(Error::parse(line_number, "too many values"));
+			}
+			element.rows.push(row);
+		}
+	}
+	Ok(())
+}
+
+fn parse_binary_body(ply: &mut Ply, counts: &[usize], bytes: &[u8], big_endian: bool) -> Result<(), Error> {
+	let mut position = 0;
+	for (element, &count) in ply.elements.iter_mut().zip(counts) {
+		let name = &element.name;
+		// every other instance reads at least one byte, so its count is limited by the size of the file
+		if count > 0 && element.properties.is_empty() {
+			return Err(Error::Invalid(format!("element `{}` has instances, but no properties", name)));
+		}
+		let mut next = |kind: PlyType| -> Result<f64, Error> {
+			let end = position + kind.size();
+			if end > bytes.len() {
+				return Err(Error::Invalid(format!("unexpected end of file in element `{}`", name)));
+			}
+			let value = kind.decode(&bytes[position..end], big_endian);
+			position = end;
+			Ok(value)
+		};
+		for _ in 0..count {
+			let mut row = Vec::with_capacity(element.properties.len());
+			for property in &element.properties {
+				row.push(match property.kind {
+					PlyPropertyKind::Scalar(kind) => PlyValue::Scalar(next(kind)?),
+					PlyPropertyKind::List { count, item } => {
+						let count = next(count)? as usize;
+						PlyValue::List((0..count).map(|_| next(item)).collect::<Result<_, _>>()?)
+					}
+				});
+			}
+			element.rows.push(row);
+		}
+	}
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use vector::Vector;
+
+	const SQUARE: &str = "ply
+format ascii 1.0
+comment a colored square
+element vertex 4
+property float x
+property float y
+property float z
+property uchar red
+property uchar green
+property uchar blue
+property float confidence
+element face 1
+property list uchar int vertex_indices
+end_header
+0 0 0 255 0 0 0.5
+1 0 0 0 255 0 1
+1 1 0 0 0 255 1
+
+0 1 0.5 10 20 30 0.25
+4 0 1 2 3
+";
+
+	#[test]
+	fn ply_ascii() {
+		let ply = Ply::parse(SQUARE.as_bytes()).unwrap();
+		assert_eq!(ply.format, PlyFormat::Ascii);
+		assert_eq!(ply.comments, vec!["a colored square".to_string()]);
+		let vertex = ply.element("vertex").unwrap();
+		assert_eq!(vertex.rows.len(), 4);
+		assert_eq!(vertex.scalars("confidence"), Some(vec![0.5, 1.0, 1.0, 0.25]));
+		assert_eq!(ply.element("face").unwrap().rows[0], vec![PlyValue::List(vec![0.0, 1.0, 2.0, 3.0])]);
+
+		let cloud: PointCloud = ply.to_point_cloud().unwrap();
+		assert_eq!(cloud.positions[3], Vector::from((0.0, 1.0, 0.5)));
+		assert_eq!(cloud.normals, None);
+		assert_eq!(cloud.colors.as_ref().unwrap()[3], [10, 20, 30, 255]);
+
+		let mesh: Mesh = ply.to_mesh().unwrap();
+		assert_eq!(mesh.faces, vec![[0, 1, 2], [0, 2, 3]]);
+		assert_eq!(mesh.uvs, None);
+
+		let mut written = vec![];
+		ply.write(&mut written).unwrap();
+		assert_eq!(Ply::parse(&written).unwrap(), ply);
+	}
+
+	#[test]
+	fn ply_binary() {
+		let mut mesh: Mesh<f64> = Ply::parse(SQUARE.as_bytes()).unwrap().to_mesh().unwrap();
+		mesh.compute_normals();
+		mesh.uvs = Some(mesh.positions.iter().map(|p| p.truncate()).collect());
+		for &format in &[PlyFormat::BinaryLittleEndian, PlyFormat::BinaryBigEndian, PlyFormat::Ascii] {
+			let ply = Ply::from_mesh(&mesh, format).unwrap();
+			let mut written = vec![];
+			ply.write(&mut written).unwrap();
+			let read = Ply::parse(&written).unwrap();
+			assert_eq!(read, ply);
+			assert_eq!(read.to_mesh::<f64>().unwrap(), mesh);
+		}
+
+		let mut written = vec![];
+		Ply::from_mesh(&mesh, PlyFormat::BinaryBigEndian).unwrap().write(&mut written).unwrap();
+		let header = String::from_utf8_lossy(&written[..written.len() - 4 * 8 * 8 - 2 * 13]).to_string();
+		assert!(header.contains("property double nx\n"));
+		assert!(header.ends_with("property list uchar uint vertex_indices\nend_header\n"));
+		// the x Component of the second vertex is 1.0
+		assert_eq!(&written[header.len() + 64..header.len() + 72], &1.0f64.to_be_bytes());
+
+		written.pop();
+		assert!(Ply::parse(&written).is_err());
+	}
+
+	#[test]
+	fn ply_point_cloud() {
+		let mut cloud = PointCloud::new(vec![Vector::from((1.0, 2.0, 3.0)), Vector::from((-1.0, 0.5, 0.0))]);
+		cloud.colors = Some(vec![[1, 2, 3, 4], [255, 255, 255, 0]]);
+		let mut written = vec![];
+		Ply::from_point_cloud(&cloud, PlyFormat::BinaryLittleEndian).unwrap().write(&mut written).unwrap();
+		assert_eq!(Ply::parse(&written).unwrap().to_point_cloud::<f32>().unwrap(), cloud);
+		assert!(Ply::parse(&written).unwrap().to_mesh::<f32>().unwrap().faces.is_empty());
+	}
+
+	#[test]
+	fn ply_errors() {
+		let error = |source: &str| match Ply::parse(source.as_bytes()) {
+			Err(Error::Parse { line, message }) => (line, message),
+			other => panic!("expected an error, got {:?}", other),
+		};
+		assert_eq!(error("obj\n"), (1, "not a PLY file".to_string()));
+		assert_eq!(error("ply\nformat ascii 1.0\nproperty float x\n"), (3, "`property` before any `element`".to_string()));
+		assert_eq!(error("ply\nformat ascii 1.0\nelement vertex 1\nproperty float128 x\n"), (4, "unknown type `float128`".to_string()));
+		assert_eq!(error("ply\nformat ascii 1.0\nelement vertex 1\nproperty float x\nend_header\n1 2\n"), (6, "too many values".to_string()));
+		assert_eq!(error("ply\nformat ascii 1.0\nelement vertex 1\nproperty float x\nend_header\nx\n"), (6, "invalid number `x`".to_string()));
+		assert!(Ply::parse(b"ply\nformat ascii 1.0\nelement vertex 2\nproperty float x\nend_header\n1\n").is_err());
+		assert!(Ply::parse(b"ply\nformat ascii 1.0\nelement face 1\nproperty float x\nend_header\n1\n").unwrap().to_mesh::<f32>().is_err());
+		let fractional = SQUARE.replace("4 0 1 2 3", "4 0 1.5 2 3");
+		assert!(Ply::parse(fractional.as_bytes()).unwrap().to_mesh::<f32>().is_err());
+		// elements without properties could claim any number of instances
+		assert!(Ply::parse(b"ply\nformat binary_little_endian 1.0\nelement foo 1000000000000000\nend_header\n").is_err_and(|e| e.to_string().contains("no properties")));
+		assert!(Ply::parse(b"ply\nformat binary_little_endian 1.0\nelement foo 0\nend_header\n").is_ok());
+	}
+
+	#[test]
+	fn ply_mismatched_lengths() {
+		let mut vertex = PlyElement::new("vertex", vec![PlyProperty::scalar("x", PlyType::Float), PlyProperty::scalar("y", PlyType::Float)]);
+		vertex.rows = vec![vec![PlyValue::Scalar(1.0), PlyValue::Scalar(2.0)], vec![PlyValue::Scalar(3.0)]];
+		assert_eq!(vertex.scalars("x"), Some(vec![1.0, 3.0]));
+		assert_eq!(vertex.scalars("y"), None);
+		let mut ply = Ply::new(PlyFormat::Ascii);
+		ply.elements.push(vertex);
+		assert_eq!(ply.write(&mut vec![]).unwrap_err().kind(), io::ErrorKind::InvalidInput);
+		ply.elements[0].rows[1].extend(vec![PlyValue::Scalar(4.0), PlyValue::Scalar(5.0)]);
+		assert_eq!(ply.write(&mut vec![]).unwrap_err().kind(), io::ErrorKind::InvalidInput);
+
+		let mut cloud: PointCloud = PointCloud::new(vec![Vector::new(), Vector::from((1.0, 0.0, 0.0))]);
+		cloud.normals = Some(vec![Vector::from((0.0, 0.0, 1.0))]);
+		assert!(Ply::from_point_cloud(&cloud, PlyFormat::Ascii).is_err());
+		cloud.normals = None;
+		cloud.colors = Some(vec![[255; 4]]);
+		assert!(Ply::from_point_cloud(&cloud, PlyFormat::Ascii).is_err());
+
+		let mut mesh: Mesh = Mesh::new(cloud.positions.clone(), vec![]);
+		mesh.uvs = Some(vec![Vec2 { x: 0.0, y: 0.0 }]);
+		assert!(Ply::from_mesh(&mesh, PlyFormat::Ascii).is_err());
+		mesh.normals = Some(vec![]);
+		mesh.uvs = None;
+		assert!(Ply::from_mesh(&mesh, PlyFormat::Ascii).is_err());
+	}
+
+	#[test]
+	fn ply_write_out_of_range() {
+		let write_error = |ply: &Ply| ply.write(&mut vec![]).unwrap_err().kind();
+		let mut ply = Ply::new(PlyFormat::BinaryLittleEndian);
+		let mut face = PlyElement::new("face", vec![PlyProperty::list("vertex_indices", PlyType::UChar, PlyType::UInt)]);
+		face.rows.push(vec![PlyValue::List((0..300).map(|i| i as f64).collect())]);
+		ply.elements.push(face);
+		assert_eq!(write_error(&ply), io::ErrorKind::InvalidData);
+		ply.elements[0].rows[0] = vec![PlyValue::List(vec![0.0, 1.0, -2.0])];
+		assert_eq!(write_error(&ply), io::ErrorKind::InvalidData);
+		ply.elements[0].rows[0] = vec![PlyValue::List(vec![0.0, 1.0, 2.0])];
+		assert!(ply.write(&mut vec![]).is_ok());
+
+		for &(kind, value) in &[(PlyType::UChar, 256.0), (PlyType::Char, -1.5), (PlyType::Short, 40_000.0), (PlyType::Float, 1e300)] {
+			let mut ply = Ply::new(PlyFormat::Ascii);
+			let mut vertex = PlyElement::new("vertex", vec![PlyProperty::scalar("x", kind)]);
+			vertex.rows.push(vec![PlyValue::Scalar(value)]);
+			ply.elements.push(vertex);
+			assert_eq!(write_error(&ply), io::ErrorKind::InvalidData, "{:?} {}", kind, value);
+		}
+	}
+}
@@ -0,0 +1,251 @@
+//! Reading and writing of [STL](https://en.wikipedia.org/wiki/STL_(file_format)) files
+//!
+//! Both the ASCII and the binary variant are supported. The facet normals stored in a file are
+//! ignored when reading, since they can be recalculated with [`Triangle::normal`](../../shapes/struct.Triangle.html#method.normal).
+
+use std::fs::File;
+use std::io::{self, BufWriter, Read, Write};
+use std::path::Path;
+
+use formats::Error;
+use shapes::{Mesh, Triangle};
+use vector::Vec3;
+use Scalar;
+
+/// The size of the header of a binary STL file
+const HEADER_SIZE: usize = 80;
+/// The size of a single Triangle in a binary STL file
+const FACET_SIZE: usize = 50;
+
+/// reads the Triangles of an STL file from `reader`, detecting whether it is ASCII or binary
+pub fn read<T: Scalar, R: Read>(mut reader: R) -> Result<Vec<Triangle<T>>, Error> {
+	let mut bytes = vec![];
+	reader.read_to_end(&mut bytes)?;
+	parse(&bytes)
+}
+
+/// reads the Triangles of the STL file at `path`, detecting whether it is ASCII or binary
+pub fn load<T: Scalar, P: AsRef<Path>>(path: P) -> Result<Vec<Triangle<T>>, Error> {
+	read(File::open(path)?)
+}
+
+/// reads an STL file into a Mesh, merging all corners that have exactly the same position
+pub fn read_mesh<T: Scalar, R: Read>(reader: R) -> Result<Mesh<T>, Error> {
+	read(reader).map(|triangles| Mesh::from_triangles(&triangles))
+}
+
+/// parses the content of an STL file, detecting whether it is ASCII or binary
+///
+/// Binary files may also start with `solid`, so a file is only treated as binary if its size
+/// matches the number of Triangles in its header.
+pub fn parse<T: Scalar>(bytes: &[u8]) -> Result<Vec<Triangle<T>>, Error> {
+	if bytes.len() >= HEADER_SIZE + 4 {
+		let count = u32::from_le_bytes([bytes[80], bytes[81], bytes[82], bytes[83]]) as usize;
+		// a size that overflows can't match the length of the file
+		let expected = count.checked_mul(FACET_SIZE).and_then(|size| size.checked_add(HEADER_SIZE + 4));
+		if expected == Some(bytes.len()) {
+			return parse_binary(bytes);
+		}
+	}
+	if bytes.starts_with(b"solid") {
+		let text = ::std::str::from_utf8(bytes).map_err(|_| Error::Invalid("ASCII STL file is not valid UTF-8".to_string()))?;
+		parse_ascii(text)
+	} else {
+		parse_binary(bytes)
+	}
+}
+
+/// parses the content of an ASCII STL file
+pub fn parse_ascii<T: Scalar>(source: &str) -> Result<Vec<Triangle<T>>, Error> {
+	let mut triangles = vec![];
+	let mut corners = vec![];
+	let mut in_loop = false;
+	for (i, line) in source.lines().enumerate() {
+		let line_number = i + 1;
+		let mut parts = line.split_whitespace();
+		match parts.next() {
+			Some("vertex") => {
+				if !in_loop {
+					return Err(Error::parse(line_number, "`vertex` outside of a loop"));
+				}
+				let numbers = parts
+					.map(|s| s.parse::<f64>().map(T::from_f64).map_err(|_| Error::parse(line_number, format!("invalid number `{}`", s))))
+					.collect::<Result<Vec<T>, Error>>()?;
+				if numbers.len() != 3 {
+					return Err(Error::parse(line_number, "`vertex` needs exactly 3 numbers"));
+				}
+				corners.push(Vec3::from((numbers[0], numbers[1], numbers[2])));
+			}
+			Some("outer") => {
+				if in_loop {
+					return Err(Error::parse(line_number, "nested loop"));
+				}
+				in_loop = true;
+				corners.clear();
+			}
+			Some("endloop") => {
+				if !in_loop {
+					return Err(Error::parse(line_number, "`endloop` outside of a loop"));
+				}
+				if corners.len() != 3 {
+					return Err(Error::parse(line_number, format!("a facet needs 3 vertices, found {}", corners.len())));
+				}
+				in_loop = false;
+				triangles.push(Triangle::new(corners[0], corners[1], corners[2]));
+				corners.clear();
+			}
+			Some("solid") | Some("endsolid") | Some("facet") | Some("endfacet") | None => {}
+			Some(other) => return Err(Error::parse(line_number, format!("unexpected `{}`", other))),
+		}
+	}
+	if in_loop {
+		return Err(Error::Invalid("unexpected end of file inside of a loop".to_string()));
+	}
+	Ok(triangles)
+}
+
+/// parses the content of a binary STL file
+pub fn parse_binary<T: Scalar>(bytes: &[u8]) -> Result<Vec<Triangle<T>>, Error> {
+	if bytes.len() < HEADER_SIZE + 4 {
+		return Err(Error::Invalid("binary STL file is too short for its header".to_string()));
+	}
+	let count = u32::from_le_bytes([bytes[80], bytes[81], bytes[82], bytes[83]]) as usize;
+	let facets = &bytes[HEADER_SIZE + 4..];
+	if count.checked_mul(FACET_SIZE).is_none_or(|size| facets.len() < size) {
+		return Err(Error::Invalid(format!("binary STL file should contain {} Triangles, but is too short", count)));
+	}
+	let float = |bytes: &[u8]| T::from_f64(f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as f64);
+	let vector = |bytes: &[u8]| Vec3::from((float(&bytes[0..]), float(&bytes[4..]), float(&bytes[8..])));
+	Ok(facets
+		.chunks(FACET_SIZE)
+		.take(count)
+		// the first 12 bytes are the normal
+		.map(|facet| Triangle::new(vector(&facet[12..]), vector(&facet[24..]), vector(&facet[36..])))
+		.collect())
+}
+
+/// calculates the normal that is written for a Triangle, which is zero for degenerate Triangles
+fn facet_normal<T: Scalar>(triangle: &Triangle<T>) -> Vec3<T> {
+	let normal = triangle.normal();
+	if normal.x.is_finite() && normal.y.is_finite() && normal.z.is_finite() {
+		normal
+	} else {
+		Vec3::new()
+	}
+}
+
+/// writes the Triangles as an ASCII STL file with the given solid `name`
+pub fn write_ascii<T: Scalar, W: Write>(triangles: &[Triangle<T>], name: &str, writer: W) -> io::Result<()> {
+	let mut writer = BufWriter::new(writer);
+	writeln!(writer, "solid {}", name)?;
+	for triangle in triangles {
+		let n = facet_normal(triangle);
+		writeln!(writer, "facet normal {} {} {}", n.x, n.y, n.z)?;
+		writeln!(writer, "  outer loop")?;
+		for c in &triangle.corners {
+			writeln!(writer, "    vertex {} {} {}", c.x, c.y, c.z)?;
+		}
+		writeln!(writer, "  endloop")?;
+		writeln!(writer, "endfacet")?;
+	}
+	writeln!(writer, "endsolid {}", name)?;
+	writer.flush()
+}
+
+/// writes the Triangles as a binary STL file
+///
+/// All values are stored as `f32`, so `f64` Triangles lose precision.
+pub fn write_binary<T: Scalar, W: Write>(triangles: &[Triangle<T>], writer: W) -> io::Result<()> {
+	if triangles.len() > u32::MAX as usize {
+		return Err(io::Error::new(io::ErrorKind::InvalidInput, "too many Triangles for a binary STL file"));
+	}
+	let mut writer = BufWriter::new(writer);
+	// the header must not start with "solid", so that it is not mistaken for an ASCII file
+	let mut header = [0u8; HEADER_SIZE];
+	let text = b"binary STL";
+	header[..text.len()].copy_from_slice(text);
+	writer.write_all(&header)?;
+	writer.write_all(&(triangles.len() as u32).to_le_bytes())?;
+	let write_vector = |writer: &mut BufWriter<W>, v: Vec3<T>| -> io::Result<()> {
+		for i in 0..3 {
+			writer.write_all(&(v[i].to_f64() as f32).to_le_bytes())?;
+		}
+		Ok(())
+	};
+	for triangle in triangles {
+		write_vector(&mut writer, facet_normal(triangle))?;
+		for &c in &triangle.corners {
+			write_vector(&mut writer, c)?;
+		}
+		// attribute byte count, which is unused
+		writer.write_all(&[0, 0])?;
+	}
+	writer.flush()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use vector::Vector;
+
+	fn tetrahedron() -> Vec<Triangle> {
+		let o = Vector::new();
+		let x = Vector::from((1.0, 0.0, 0.0));
+		let y = Vector::from((0.0, 1.0, 0.0));
+		let z = Vector::from((0.0, 0.0, 1.5));
+		vec![Triangle::new(o, y, x), Triangle::new(o, x, z), Triangle::new(o, z, y), Triangle::new(x, y, z)]
+	}
+
+	#[test]
+	fn stl_ascii() {
+		let mut written = vec![];
+		write_ascii(&tetrahedron(), "tetra", &mut written).unwrap();
+		let text = String::from_utf8(written.clone()).unwrap();
+		assert!(text.starts_with("solid tetra\nfacet normal 0 0 -1\n  outer loop\n    vertex 0 0 0\n    vertex 0 1 0\n"));
+		assert!(text.ends_with("endsolid tetra\n"));
+		assert_eq!(read::<f32, _>(&written[..]).unwrap(), tetrahedron());
+
+		let error = |source: &str| match parse_ascii::<f32>(source) {
+			Err(Error::Parse { line, message }) => (line, message),
+			other => panic!("expected an error, got {:?}", other),
+		};
+		assert_eq!(error("solid\nfacet normal 0 0 1\nouter loop\nvertex 0 0 0\nendloop"), (5, "a facet needs 3 vertices, found 1".to_string()));
+		assert_eq!(error("solid\nvertex 0 0 0"), (2, "`vertex` outside of a loop".to_string()));
+		assert_eq!(
+			error("solid\nfacet normal 0 0 1\nouter loop\nvertex 0 0 0\nvertex 1 0 0\nvertex 0 1 0\nendloop\nendloop\nendfacet"),
+			(8, "`endloop` outside of a loop".to_string())
+		);
+		assert_eq!(error("solid\nouter loop\nvertex 0 x 0"), (3, "invalid number `x`".to_string()));
+		assert_eq!(error("solid\nfoo"), (2, "unexpected `foo`".to_string()));
+		assert!(parse_ascii::<f32>("solid\nouter loop\n").is_err());
+	}
+
+	#[test]
+	fn stl_binary() {
+		let mut written = vec![];
+		write_binary(&tetrahedron(), &mut written).unwrap();
+		assert_eq!(written.len(), 84 + 4 * 50);
+		assert_eq!(&written[..10], b"binary STL");
+		// the normal of the first Triangle
+		assert_eq!(&written[84..96], &[0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 128, 191]);
+		assert_eq!(read::<f32, _>(&written[..]).unwrap(), tetrahedron());
+		assert_eq!(parse_binary::<f64>(&written).unwrap()[3].corners[2], Vec3::from((0.0, 0.0, 1.5)));
+
+		// a binary file with a header starting with "solid" is still detected correctly
+		written[..5].copy_from_slice(b"solid");
+		assert_eq!(parse::<f32>(&written).unwrap(), tetrahedron());
+
+		written.truncate(200);
+		assert!(parse_binary::<f32>(&written).is_err());
+	}
+
+	#[test]
+	fn stl_mesh() {
+		let mut written = vec![];
+		write_binary(&tetrahedron(), &mut written).unwrap();
+		let mesh: Mesh = read_mesh(&written[..]).unwrap();
+		assert_eq!(mesh.vertex_count(), 4);
+		assert_eq!(mesh.face_count(), 4);
+		assert!((mesh.volume() - 0.25).abs() <= 1e-6);
+	}
+}
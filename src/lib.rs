@@ -1,13 +1,43 @@
 //! # 3D Utility Library
 //!
 //! A Library for general purpose 3D Mathematics.
+//!
+//! All Types are generic over a [`Scalar`](trait.Scalar.html) (`f32` or `f64`).
+//! The unprefixed names like [`Vector`](type.Vector.html) and [`Matrix`](type.Matrix.html)
+//! use `f32`, while the `D`-prefixed aliases use `f64`.
+
+mod scalar;
+pub use scalar::Scalar;
+
+mod clip_space;
+pub use clip_space::{ClipSpace, DepthRange, Handedness};
+
+mod euler;
+pub use euler::EulerOrder;
 
 mod matrix;
-pub use matrix::Matrix;
+pub use matrix::{DMatrix, Mat4, Matrix};
+
+mod matrix3;
+pub use matrix3::{DMatrix3, Mat3, Matrix3};
+
+mod matrix2;
+pub use matrix2::{DMatrix2, Mat2, Matrix2};
 
 mod vector;
-pub use vector::Vector;
+pub use vector::{DVector, Vec3, Vector};
+
+mod vector2;
+pub use vector2::{DVector2, Vec2, Vector2};
+
+mod vector4;
+pub use vector4::{DVector4, Vec4, Vector4};
+
+mod quaternion;
+pub use quaternion::{DQuaternion, Quat, Quaternion};
 
 pub mod shapes;
 
 pub mod ray_tracing;
+
+pub mod formats;
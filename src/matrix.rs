@@ -1,15 +1,28 @@
-#![allow(clippy::needless_range_loop, clippy::new_without_default_derive)]
+#![allow(clippy::needless_range_loop, clippy::new_without_default)]
 
-use Vector;
+use clip_space::ClipSpace;
+use euler::EulerOrder;
+use matrix3::Mat3;
+use quaternion::Quat;
+use vector::Vec3;
+use vector4::Vec4;
+use Scalar;
 
-/// A 4D Matrix for calculating with 3D Vectors
+/// A 4D Matrix for calculating with 3D Vectors of the Scalar Type `T`
+///
+/// Most code should use the [`Matrix`](type.Matrix.html) (`f32`) or [`DMatrix`](type.DMatrix.html) (`f64`) aliases.
 #[derive(Copy, Clone, Debug)]
-pub struct Matrix {
+pub struct Mat4<T: Scalar> {
 	/// The internal data of the Matrix
-	pub data: [[f32; 4]; 4],
+	pub data: [[T; 4]; 4],
 }
 
-impl Matrix {
+/// A 4D Matrix with `f32` entries
+pub type Matrix = Mat4<f32>;
+/// A 4D Matrix with `f64` entries
+pub type DMatrix = Mat4<f64>;
+
+impl<T: Scalar> Mat4<T> {
 	/// Creates a new Matrix with all entries set to 0
 	///
 	/// ```text
@@ -18,9 +31,9 @@ impl Matrix {
 	/// [0  0  0  0]
 	/// [0  0  0  0]
 	/// ```
-	pub fn new() -> Matrix {
-		Matrix {
-			data: Default::default()
+	pub fn new() -> Mat4<T> {
+		Mat4 {
+			data: [[T::ZERO; 4]; 4],
 		}
 	}
 	/// Creates an Identity Matrix with the diagonal set to 1 and everything else set to 0
@@ -31,83 +44,159 @@ impl Matrix {
 	/// [0  0  1  0]
 	/// [0  0  0  1]
 	/// ```
-	pub fn identity() -> Matrix {
-		Matrix {
-			data: [
-				[1.0, 0.0, 0.0, 0.0],
-				[0.0, 1.0, 0.0, 0.0],
-				[0.0, 0.0, 1.0, 0.0],
-				[0.0, 0.0, 0.0, 1.0],
-			],
+	pub fn identity() -> Mat4<T> {
+		let mut mat = Mat4::new();
+		for i in 0..4 {
+			mat[i][i] = T::ONE;
 		}
+		mat
 	}
 	/// Creates a LookAt Matrix for a Camera at `position` facing `looking_at` with up Vector `up`
-	pub fn look_at(position: Vector, looking_at: Vector, up: Vector) -> Matrix {
-		Matrix::view(position, looking_at - position, up)
+	pub fn look_at(position: Vec3<T>, looking_at: Vec3<T>, up: Vec3<T>) -> Mat4<T> {
+		Mat4::view(position, looking_at - position, up)
 	}
 	/// Creates a View Matrix for a Camera at `position` facing in `direction` with up Vector `up`
-	pub fn view(position: Vector, direction: Vector, up: Vector) -> Matrix {
+	///
+	/// In view space, the Camera is at the origin looking along +z, with +y up and +x to the
+	/// right. This matches the conventions of [projection](#method.projection).
+	pub fn view(position: Vec3<T>, direction: Vec3<T>, up: Vec3<T>) -> Mat4<T> {
 		let f = direction.norm();
 
 		let s = up.cross(f).norm();
 
 		let u = f.cross(s).norm();
 
-		let p = Vector {
+		let p = Vec3 {
 			x: -position * s,
 			y: -position * u,
 			z: -position * f,
 		};
 
-		Matrix {
+		let (zero, one) = (T::ZERO, T::ONE);
+		Mat4 {
 			data: [
-				[s.x, s.x, s.x, p.x],
-				[u.y, u.y, u.y, p.y],
-				[f.z, f.z, f.z, p.z],
-				[0.0, 0.0, 0.0, 1.0],
+				[s.x, s.y, s.z, p.x],
+				[u.x, u.y, u.z, p.y],
+				[f.x, f.y, f.z, p.z],
+				[zero, zero, zero, one],
 			],
 		}
 	}
 	/// Creates a Projection Matrix for a ViewPort with dimensions `(width, height)`, a Field of View `fov` in Radians and the `near` and `far` Boundaries
-	pub fn projection((width, height): (usize, usize), fov: f32, near: f32, far: f32) -> Matrix {
-		let aspect_ratio = height as f32 / width as f32;
+	pub fn projection((width, height): (usize, usize), fov: T, near: T, far: T) -> Mat4<T> {
+		let (zero, one, two) = (T::ZERO, T::ONE, T::TWO);
+
+		let aspect_ratio = T::from_f64(height as f64 / width as f64);
 
-		let f = 1.0 / (fov / 2.0).tan();
+		let f = one / (fov / two).tan();
 
-		let dz = -(2.0 * far * near) / (far - near);
+		let dz = -(two * far * near) / (far - near);
 
-		Matrix {
+		Mat4 {
 			data: [
-				[f * aspect_ratio, 0.0, 0.0, 0.0],
-				[0.0, f, 0.0, 0.0],
-				[0.0, 0.0, (far + near) / (far - near), dz],
-				[0.0, 0.0, 1.0, 0.0],
+				[f * aspect_ratio, zero, zero, zero],
+				[zero, f, zero, zero],
+				[zero, zero, (far + near) / (far - near), dz],
+				[zero, zero, one, zero],
 			],
 		}
 	}
 	/// Creates a Frustum Matrix with the given Boundaries
 	#[allow(clippy::many_single_char_names)]
-	pub fn frustum(left: f32, right: f32, top: f32, bottom: f32, near: f32, far: f32) -> Matrix {
+	pub fn frustum(left: T, right: T, top: T, bottom: T, near: T, far: T) -> Mat4<T> {
 		let (l, r, t, b, n, f) = (left, right, top, bottom, near, far);
+		let (zero, one, two) = (T::ZERO, T::ONE, T::TWO);
 
 		let rml = right - left;
 		let tmb = top - bottom;
 		let fmn = far - near;
 
-		Matrix {
+		Mat4 {
+			data: [
+				[two * n / rml, zero, (r + l) / rml, zero],
+				[zero, two * n / tmb, (t + b) / tmb, zero],
+				[zero, zero, -(f + n) / fmn, -two * f * n / fmn],
+				[zero, zero, -one, zero],
+			],
+		}
+	}
+	/// Creates a Perspective Projection Matrix with a vertical Field of View `fov_y` in Radians,
+	/// an `aspect` ratio (width / height) and the `near` and `far` Boundaries (as positive distances)
+	///
+	/// The Points on the near and far plane are mapped to the ends of the depth Range of `clip`.
+	pub fn perspective(fov_y: T, aspect: T, near: T, far: T, clip: ClipSpace) -> Mat4<T> {
+		let (a, b) = clip.depth_bounds();
+		let (a, b) = (T::from_f64(a), T::from_f64(b));
+		let depth_scale = (b * far - a * near) / (far - near);
+		let depth_offset = (a - b) * near * far / (far - near);
+		Mat4::perspective_depth(fov_y, aspect, depth_scale, depth_offset, clip)
+	}
+	/// Creates a Perspective Projection Matrix like [perspective](#method.perspective), but with the far plane at infinity
+	///
+	/// Points infinitely far away are mapped to the far end of the depth Range of `clip`. This
+	/// is usually combined with [reverse-Z](struct.ClipSpace.html#structfield.reverse_z).
+	pub fn perspective_infinite(fov_y: T, aspect: T, near: T, clip: ClipSpace) -> Mat4<T> {
+		let (a, b) = clip.depth_bounds();
+		let (a, b) = (T::from_f64(a), T::from_f64(b));
+		Mat4::perspective_depth(fov_y, aspect, b, (a - b) * near, clip)
+	}
+	/// Creates a Perspective Projection Matrix where the clip space depth is `scale * d + offset`
+	/// for a distance `d` along the looking direction
+	fn perspective_depth(fov_y: T, aspect: T, scale: T, offset: T, clip: ClipSpace) -> Mat4<T> {
+		let zero = T::ZERO;
+		let forward = T::from_f64(clip.forward_z());
+		let f = T::ONE / (fov_y / T::TWO).tan();
+		Mat4 {
+			data: [
+				[f / aspect, zero, zero, zero],
+				[zero, f, zero, zero],
+				[zero, zero, scale * forward, offset],
+				[zero, zero, forward, zero],
+			],
+		}
+	}
+	/// Creates an Orthographic Projection Matrix with the given Boundaries, using the OpenGL
+	/// conventions (see [ClipSpace](struct.ClipSpace.html))
+	///
+	/// `near` and `far` are distances along the looking direction.
+	pub fn orthographic(left: T, right: T, bottom: T, top: T, near: T, far: T) -> Mat4<T> {
+		Mat4::orthographic_with(left, right, bottom, top, near, far, ClipSpace::default())
+	}
+	/// Creates an Orthographic Projection Matrix with the given Boundaries, following the conventions of `clip`
+	///
+	/// `near` and `far` are distances along the looking direction.
+	///
+	/// ```
+	/// # use utils_3d::{ClipSpace, Matrix, Vector};
+	/// let m = Matrix::orthographic_with(-2.0, 2.0, -1.0, 1.0, 1.0, 11.0, ClipSpace::DIRECT3D);
+	/// assert_eq!(m * Vector::from((2.0, 1.0, 11.0)), Vector::from((1.0, 1.0, 1.0)));
+	/// assert_eq!(m * Vector::from((-2.0, -1.0, 1.0)), Vector::from((-1.0, -1.0, 0.0)));
+	/// ```
+	#[allow(clippy::too_many_arguments)]
+	pub fn orthographic_with(left: T, right: T, bottom: T, top: T, near: T, far: T, clip: ClipSpace) -> Mat4<T> {
+		let (zero, one, two) = (T::ZERO, T::ONE, T::TWO);
+		let (a, b) = clip.depth_bounds();
+		let (a, b) = (T::from_f64(a), T::from_f64(b));
+		let forward = T::from_f64(clip.forward_z());
+
+		let rml = right - left;
+		let tmb = top - bottom;
+		let depth_scale = (b - a) / (far - near);
+
+		Mat4 {
 			data: [
-				[2.0 * n / rml, 0.0, (r + l) / rml, 0.0],
-				[0.0, 2.0 * n / tmb, (t + b) / tmb, 0.0],
-				[0.0, 0.0, -(f + n) / fmn, -2.0 * f * n / fmn],
-				[0.0, 0.0, -1.0, 0.0],
+				[two / rml, zero, zero, -(right + left) / rml],
+				[zero, two / tmb, zero, -(top + bottom) / tmb],
+				[zero, zero, depth_scale * forward, a - depth_scale * near],
+				[zero, zero, zero, one],
 			],
 		}
 	}
 	/// Returns a Matrix created from Transposing this Matrix
 	///
 	/// A Transposed Matrix is mirrored along the diagonal, so that rows and columns are swapped
-	pub fn transposed(&self) -> Matrix {
-		let mut mat = Matrix::new();
+	pub fn transposed(&self) -> Mat4<T> {
+		let mut mat = Mat4::new();
 		mat.iter_mut().enumerate().for_each(|(x, m)| {
 			self.iter().enumerate().for_each(|(y, s)| {
 				m[y] = s[x];
@@ -115,61 +204,399 @@ impl Matrix {
 		});
 		mat
 	}
+	/// Calculates the determinant of the 3x3 Matrix that remains after removing `row` and `col`
+	pub fn minor(&self, row: usize, col: usize) -> T {
+		let mut m = [[T::ZERO; 3]; 3];
+		for (y, src_y) in (0..4).filter(|&y| y != row).enumerate() {
+			for (x, src_x) in (0..4).filter(|&x| x != col).enumerate() {
+				m[y][x] = self[src_y][src_x];
+			}
+		}
+		m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+			- m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+			+ m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+	}
+	/// Calculates the cofactor of the entry at `row` and `col`
+	///
+	/// The cofactor is the [minor](#method.minor) with the sign `(-1)^(row + col)`
+	pub fn cofactor(&self, row: usize, col: usize) -> T {
+		let minor = self.minor(row, col);
+		if (row + col).is_multiple_of(2) {
+			minor
+		} else {
+			-minor
+		}
+	}
+	/// Calculates the [determinant](https://en.wikipedia.org/wiki/Determinant) of the Matrix
+	///
+	/// ```
+	/// # use utils_3d::Matrix;
+	/// let mut m = Matrix::identity();
+	/// m[0][0] = 2.0;
+	/// m[1][1] = 3.0;
+	/// assert_eq!(m.determinant(), 6.0);
+	/// ```
+	pub fn determinant(&self) -> T {
+		(0..4).map(|x| self[0][x] * self.cofactor(0, x)).sum()
+	}
+	/// Returns the [adjugate](https://en.wikipedia.org/wiki/Adjugate_matrix) of the Matrix
+	///
+	/// The adjugate is the transposed Matrix of cofactors. It is the inverse multiplied by the determinant.
+	pub fn adjugate(&self) -> Mat4<T> {
+		let mut mat = Mat4::new();
+		for y in 0..4 {
+			for x in 0..4 {
+				mat[x][y] = self.cofactor(y, x);
+			}
+		}
+		mat
+	}
+	/// Checks if the bottom row of the Matrix is exactly `[0, 0, 0, 1]`
+	///
+	/// This is the case for any combination of translations, rotations, scales and shears.
+	pub fn is_affine(&self) -> bool {
+		self[3] == [T::ZERO, T::ZERO, T::ZERO, T::ONE]
+	}
+	/// Calculates the inverse of the Matrix
+	///
+	/// returns None if the Matrix is singular (the determinant is 0 or the inverse is not finite).
+	///
+	/// Uses [try_inverse_affine](#method.try_inverse_affine) if the Matrix [is affine](#method.is_affine).
+	///
+	/// ```
+	/// # use utils_3d::{Matrix, Vector};
+	/// let m = Matrix::translate(Vector::from((1.0, 2.0, 3.0)));
+	/// let p = Vector::from((4.0, 5.0, 6.0));
+	/// assert_eq!(m.try_inverse().unwrap() * (m * p), p);
+	///
+	/// assert!(Matrix::new().try_inverse().is_none());
+	/// ```
+	pub fn try_inverse(&self) -> Option<Mat4<T>> {
+		if self.is_affine() {
+			return self.try_inverse_affine();
+		}
+		let det = self.determinant();
+		if det == T::ZERO {
+			return None;
+		}
+		Some(self.adjugate() * (T::ONE / det)).filter(Mat4::is_finite)
+	}
+	/// Calculates the inverse of an affine Matrix
+	///
+	/// This is faster than the general [try_inverse](#method.try_inverse), since only the upper-left
+	/// 3x3 block has to be inverted. The inverse translation is then calculated from that block.
+	///
+	/// The result is only correct if the Matrix [is affine](#method.is_affine). Returns None if
+	/// the upper-left 3x3 block is singular.
+	pub fn try_inverse_affine(&self) -> Option<Mat4<T>> {
+		let mut mat = Mat4::from(Mat3::from(*self).try_inverse()?);
+		for y in 0..3 {
+			mat[y][3] = -(0..3).map(|x| mat[y][x] * self[x][3]).sum::<T>();
+		}
+		Some(mat).filter(Mat4::is_finite)
+	}
+	/// Transforms a Point by this Matrix
+	///
+	/// The Point is extended with `w = 1`, so translations are applied. The result is divided by
+	/// the resulting w, unless it is 0 (the Point was projected to infinity).
+	///
+	/// This is the same as `self * point`
+	pub fn transform_point(&self, point: Vec3<T>) -> Vec3<T> {
+		let out = self.transform_homogeneous(point, T::ONE);
+		if out.w == T::ZERO || out.w == T::ONE {
+			out.truncate()
+		} else {
+			out.truncate() / out.w
+		}
+	}
+	/// Transforms a direction Vector by this Matrix
+	///
+	/// The Vector is extended with `w = 0`, so translations and projections are ignored.
+	///
+	/// ```
+	/// # use utils_3d::{Matrix, Vector};
+	/// let m = Matrix::translate(Vector::from((5.0, 0.0, 0.0)));
+	/// let v = Vector::from((1.0, 2.0, 3.0));
+	/// assert_eq!(m.transform_vector(v), v);
+	/// assert_eq!(m.transform_point(v), Vector::from((6.0, 2.0, 3.0)));
+	/// ```
+	pub fn transform_vector(&self, vector: Vec3<T>) -> Vec3<T> {
+		self.transform_homogeneous(vector, T::ZERO).truncate()
+	}
+	/// Returns the Matrix for transforming surface Normals, which is the inverse-transpose of the upper-left 3x3 block
+	///
+	/// Normals have to be multiplied with this Matrix to stay perpendicular to the transformed
+	/// surface under non-uniform scaling and shearing. If the block is singular, its cofactor
+	/// Matrix is returned instead, which still points Normals in the correct direction.
+	pub fn normal_matrix(&self) -> Mat3<T> {
+		let block = Mat3::from(*self);
+		// the inverse-transpose is the cofactor Matrix divided by the determinant
+		let cofactors = block.adjugate().transposed();
+		let det = block.determinant();
+		if det == T::ZERO {
+			cofactors
+		} else {
+			cofactors * (T::ONE / det)
+		}
+	}
+	/// Transforms a surface Normal by this Matrix
+	///
+	/// This is the same as `self.normal_matrix() * normal`, see [normal_matrix](#method.normal_matrix).
+	///
+	/// The result is not normalized.
+	pub fn transform_normal(&self, normal: Vec3<T>) -> Vec3<T> {
+		self.normal_matrix() * normal
+	}
+	/// Multiplies the Matrix with `vector` extended by `w`, without any perspective division
+	///
+	/// Returns the raw homogeneous coordinates, e.g. in clip space for a Projection Matrix.
+	/// This is the same as `self * vector.extend(w)`
+	pub fn transform_homogeneous(&self, vector: Vec3<T>, w: T) -> Vec4<T> {
+		*self * vector.extend(w)
+	}
+	/// Checks if all entries of the Matrix are finite
+	pub fn is_finite(&self) -> bool {
+		self.iter().all(|row| row.iter().all(|v| v.is_finite()))
+	}
 	/// Creates a Translation Matrix for a translation by `delta`
-	pub fn translate(delta: Vector) -> Matrix {
-		let mut mat = Matrix::identity();
+	pub fn translate(delta: Vec3<T>) -> Mat4<T> {
+		let mut mat = Mat4::identity();
 		mat[0][3] = delta.x;
 		mat[1][3] = delta.y;
 		mat[2][3] = delta.z;
 		mat
 	}
+	/// Creates a Scaling Matrix that scales each Axis by the corresponding Component of `factor`
+	pub fn scale(factor: Vec3<T>) -> Mat4<T> {
+		let mut mat = Mat4::identity();
+		mat[0][0] = factor.x;
+		mat[1][1] = factor.y;
+		mat[2][2] = factor.z;
+		mat
+	}
+	/// Creates a Scaling Matrix that scales all Axes by `factor`
+	pub fn scale_uniform(factor: T) -> Mat4<T> {
+		Mat4::scale(Vec3 {
+			x: factor,
+			y: factor,
+			z: factor,
+		})
+	}
+	/// Creates a Shearing Matrix that shifts the x Component by `y * by_y + z * by_z`
+	pub fn shear_x(by_y: T, by_z: T) -> Mat4<T> {
+		let mut mat = Mat4::identity();
+		mat[0][1] = by_y;
+		mat[0][2] = by_z;
+		mat
+	}
+	/// Creates a Shearing Matrix that shifts the y Component by `x * by_x + z * by_z`
+	pub fn shear_y(by_x: T, by_z: T) -> Mat4<T> {
+		let mut mat = Mat4::identity();
+		mat[1][0] = by_x;
+		mat[1][2] = by_z;
+		mat
+	}
+	/// Creates a Shearing Matrix that shifts the z Component by `x * by_x + y * by_y`
+	pub fn shear_z(by_x: T, by_y: T) -> Mat4<T> {
+		let mut mat = Mat4::identity();
+		mat[2][0] = by_x;
+		mat[2][1] = by_y;
+		mat
+	}
+	/// Creates a Transformation Matrix from a translation, a rotation and a scale
+	///
+	/// The Transformations are applied in the order scale, rotation, translation, so the result is
+	/// `Matrix::translate(translation) * Matrix::from(rotation) * Matrix::scale(scale)`
+	pub fn from_trs(translation: Vec3<T>, rotation: Quat<T>, scale: Vec3<T>) -> Mat4<T> {
+		let mut mat = rotation.to_matrix();
+		for y in 0..3 {
+			for x in 0..3 {
+				mat[y][x] *= scale[x];
+			}
+			mat[y][3] = translation[y];
+		}
+		mat
+	}
+	/// Splits an affine Matrix into translation, rotation and scale, so that
+	/// [from_trs](#method.from_trs) recreates the Matrix
+	///
+	/// Returns None if the Matrix is not [affine](#method.is_affine) or if any Axis is scaled to 0.
+	///
+	/// A Matrix that mirrors (has a negative determinant) is represented with a negative x scale.
+	/// Any shearing of the Matrix is lost.
+	///
+	/// ```
+	/// # use utils_3d::{Matrix, Quaternion, Vector};
+	/// let t = Vector::from((1.0, 2.0, 3.0));
+	/// let r = Quaternion::from_euler(0.1, 0.2, 0.3);
+	/// let s = Vector::from((-2.0, 1.0, 1.0));
+	/// let (t2, r2, s2) = Matrix::from_trs(t, r, s).decompose().unwrap();
+	/// assert_eq!(t2, t);
+	/// assert!((s2 - s).length() <= 1e-6);
+	/// assert!(r2.dot(r).abs() >= 1.0 - 1e-6);
+	/// ```
+	pub fn decompose(&self) -> Option<(Vec3<T>, Quat<T>, Vec3<T>)> {
+		if !self.is_affine() {
+			return None;
+		}
+		let translation = Vec3::from((self[0][3], self[1][3], self[2][3]));
+		let column = |x: usize| Vec3::from((self[0][x], self[1][x], self[2][x]));
+		let mut scale = Vec3::from((column(0).length(), column(1).length(), column(2).length()));
+		if self.minor(3, 3) < T::ZERO {
+			scale.x = -scale.x;
+		}
+		if scale.x == T::ZERO || scale.y == T::ZERO || scale.z == T::ZERO {
+			return None;
+		}
+		let mut rotation = Mat4::identity();
+		for y in 0..3 {
+			for x in 0..3 {
+				rotation[y][x] = self[y][x] / scale[x];
+			}
+		}
+		Some((translation, Quat::from_matrix(&rotation), scale))
+	}
 	/// Creates a Rotation Matrix for rotating around the x Axis by `radians`
-	pub fn rot_x(radians: f32) -> Matrix {
+	pub fn rot_x(radians: T) -> Mat4<T> {
 		let (s, c) = radians.sin_cos();
-		Matrix {
+		let (zero, one) = (T::ZERO, T::ONE);
+		Mat4 {
 			data: [
-				[1.0, 0.0, 0.0, 0.0],
-				[0.0, c, -s, 0.0],
-				[0.0, s, c, 0.0],
-				[0.0, 0.0, 0.0, 1.0],
+				[one, zero, zero, zero],
+				[zero, c, -s, zero],
+				[zero, s, c, zero],
+				[zero, zero, zero, one],
 			],
 		}
 	}
 	/// Creates a Rotation Matrix for rotating around the y Axis by `radians`
-	pub fn rot_y(radians: f32) -> Matrix {
+	pub fn rot_y(radians: T) -> Mat4<T> {
 		let (s, c) = radians.sin_cos();
-		Matrix {
+		let (zero, one) = (T::ZERO, T::ONE);
+		Mat4 {
 			data: [
-				[c, 0.0, s, 0.0],
-				[0.0, 1.0, 0.0, 0.0],
-				[-s, 0.0, c, 0.0],
-				[0.0, 0.0, 0.0, 1.0],
+				[c, zero, s, zero],
+				[zero, one, zero, zero],
+				[-s, zero, c, zero],
+				[zero, zero, zero, one],
 			],
 		}
 	}
 	/// Creates a Rotation Matrix for rotating around the z Axis by `radians`
-	pub fn rot_z(radians: f32) -> Matrix {
+	pub fn rot_z(radians: T) -> Mat4<T> {
 		let (s, c) = radians.sin_cos();
-		Matrix {
+		let (zero, one) = (T::ZERO, T::ONE);
+		Mat4 {
 			data: [
-				[c, -s, 0.0, 0.0],
-				[s, c, 0.0, 0.0],
-				[0.0, 0.0, 1.0, 0.0],
-				[0.0, 0.0, 0.0, 1.0],
+				[c, -s, zero, zero],
+				[s, c, zero, zero],
+				[zero, zero, one, zero],
+				[zero, zero, zero, one],
 			],
 		}
 	}
+	/// Creates a Rotation Matrix for rotating around `axis` by `radians`
+	///
+	/// `axis` does not have to be normalized. Uses [Rodrigues' rotation formula](https://en.wikipedia.org/wiki/Rodrigues%27_rotation_formula):
+	///
+	/// ```text
+	/// R = I * cos + [axis]x * sin + (axis * axis^T) * (1 - cos)
+	/// ```
+	pub fn rotation(axis: Vec3<T>, radians: T) -> Mat4<T> {
+		let k = axis.norm();
+		let (s, c) = radians.sin_cos();
+		let t = T::ONE - c;
+		let (zero, one) = (T::ZERO, T::ONE);
+		Mat4 {
+			data: [
+				[c + k.x * k.x * t, k.x * k.y * t - k.z * s, k.x * k.z * t + k.y * s, zero],
+				[k.y * k.x * t + k.z * s, c + k.y * k.y * t, k.y * k.z * t - k.x * s, zero],
+				[k.z * k.x * t - k.y * s, k.z * k.y * t + k.x * s, c + k.z * k.z * t, zero],
+				[zero, zero, zero, one],
+			],
+		}
+	}
+	/// Creates a Rotation Matrix for rotating around the Axis with the index `axis` (`0` = x, `1` = y, `2` = z)
+	fn rot_axis(axis: usize, radians: T) -> Mat4<T> {
+		match axis {
+			0 => Mat4::rot_x(radians),
+			1 => Mat4::rot_y(radians),
+			2 => Mat4::rot_z(radians),
+			_ => panic!("Axis index out of Range: {} given, max 2", axis),
+		}
+	}
+	/// Creates a Rotation Matrix from Euler angles in Radians
+	///
+	/// `a`, `b` and `c` are the angles around the first, second and third Axis of `order`.
+	/// See [EulerOrder](enum.EulerOrder.html) for details.
+	///
+	/// ```
+	/// # use utils_3d::{EulerOrder, Matrix, Vector};
+	/// let m = Matrix::from_euler(EulerOrder::XYZ, 0.1, 0.2, 0.3);
+	/// let expected = Matrix::rot_z(0.3) * Matrix::rot_y(0.2) * Matrix::rot_x(0.1);
+	/// let v = Vector::from((1.0, 2.0, 3.0));
+	/// assert_eq!(m * v, expected * v);
+	/// ```
+	pub fn from_euler(order: EulerOrder, a: T, b: T, c: T) -> Mat4<T> {
+		let [i, j, k] = order.axes();
+		Mat4::rot_axis(k, c) * Mat4::rot_axis(j, b) * Mat4::rot_axis(i, a)
+	}
+	/// Extracts the Euler angles `(a, b, c)` of the Rotation part of this Matrix, so that
+	/// `Matrix::from_euler(order, a, b, c)` recreates the Rotation
+	///
+	/// The upper-left 3x3 block of the Matrix should be a pure Rotation.
+	///
+	/// The middle angle `b` is in `[-π/2, π/2]` for Tait-Bryan orders and in `[0, π]` for proper Euler orders,
+	/// the other angles are in `[-π, π]`.
+	///
+	/// In case of [Gimbal lock](https://en.wikipedia.org/wiki/Gimbal_lock), the first and last Axis
+	/// coincide and only their combined angle can be recovered. `c` is then always 0.
+	pub fn to_euler(&self, order: EulerOrder) -> (T, T, T) {
+		let [i, j, third] = order.axes();
+		let k = 3 - i - j;
+		// Permute the Axes so that the order becomes XYZ (or XYX). If that permutation is a
+		// reflection, it negates all angles.
+		let m = |y: usize, x: usize| {
+			let axes = [i, j, k];
+			self[axes[y]][axes[x]]
+		};
+		let parity = if (j + 3 - i) % 3 == 1 { T::ONE } else { -T::ONE };
+		let threshold = T::EPSILON.sqrt();
+
+		let (a, b, c) = if i == third {
+			let sin_b = (m(0, 1) * m(0, 1) + m(0, 2) * m(0, 2)).sqrt();
+			let b = sin_b.atan2(m(0, 0));
+			if sin_b <= threshold {
+				return ((-m(1, 2)).atan2(m(1, 1)) * parity, b, T::ZERO);
+			}
+			(m(0, 1).atan2(m(0, 2)), b, m(1, 0).atan2(-m(2, 0)))
+		} else {
+			let cos_b = (m(0, 0) * m(0, 0) + m(1, 0) * m(1, 0)).sqrt();
+			let b = (-m(2, 0)).atan2(cos_b);
+			if cos_b <= threshold {
+				return ((-m(1, 2)).atan2(m(1, 1)) * parity, b * parity, T::ZERO);
+			}
+			(m(2, 1).atan2(m(2, 2)), b, m(1, 0).atan2(m(0, 0)))
+		};
+		if i == third && parity < T::ZERO {
+			// keep b in [0, π] by using rot(c + π) * rot(b) * rot(a + π) == rot(c) * rot(-b) * rot(a)
+			let wrap = |x: T| if x > T::PI { x - T::TWO * T::PI } else { x };
+			(wrap(T::PI - a), b, wrap(T::PI - c))
+		} else {
+			(a * parity, b * parity, c * parity)
+		}
+	}
 }
 
 use std::ops::*;
 
-impl Mul for Matrix {
-	type Output = Matrix;
-	fn mul(self, rhs: Self) -> Matrix {
-		let mut ret = Matrix::new();
+impl<T: Scalar> Mul for Mat4<T> {
+	type Output = Mat4<T>;
+	fn mul(self, rhs: Self) -> Mat4<T> {
+		let mut ret = Mat4::new();
 		for y in 0..4 {
 			for x in 0..4 {
-				let mut sum = 0.0;
+				let mut sum = T::ZERO;
 				for i in 0..4 {
 					sum += self[y][i] * rhs[i][x];
 				}
@@ -179,31 +606,22 @@ impl Mul for Matrix {
 		ret
 	}
 }
-impl MulAssign for Matrix {
+impl<T: Scalar> MulAssign for Mat4<T> {
 	fn mul_assign(&mut self, rhs: Self) {
 		*self = *self * rhs;
 	}
 }
 
-impl Mul<Vector> for Matrix {
-	type Output = Vector;
-	fn mul(self, rhs: Vector) -> Vector {
-		let mut out = [0.0; 4];
-		let rhs = [rhs.x, rhs.y, rhs.z, 1.0];
-
-		for i in 0..4 {
-			for j in 0..4 {
-				out[i] += self[i][j] * rhs[j];
-			}
-		}
-
-		Vector::from(&out[0..3]) * (1.0 / out[3])
+impl<T: Scalar> Mul<Vec3<T>> for Mat4<T> {
+	type Output = Vec3<T>;
+	fn mul(self, rhs: Vec3<T>) -> Vec3<T> {
+		self.transform_point(rhs)
 	}
 }
 
-impl Mul<f32> for Matrix {
-	type Output = Matrix;
-	fn mul(self, rhs: f32) -> Matrix {
+impl<T: Scalar> Mul<T> for Mat4<T> {
+	type Output = Mat4<T>;
+	fn mul(self, rhs: T) -> Mat4<T> {
 		let mut out = self;
 		for i in 0..4 {
 			for j in 0..4 {
@@ -214,9 +632,9 @@ impl Mul<f32> for Matrix {
 	}
 }
 
-impl Add<Matrix> for Matrix {
-	type Output = Matrix;
-	fn add(self, rhs: Matrix) -> Matrix {
+impl<T: Scalar> Add<Mat4<T>> for Mat4<T> {
+	type Output = Mat4<T>;
+	fn add(self, rhs: Mat4<T>) -> Mat4<T> {
 		let mut out = self;
 		for i in 0..4 {
 			for j in 0..4 {
@@ -227,9 +645,9 @@ impl Add<Matrix> for Matrix {
 	}
 }
 
-impl Sub<Matrix> for Matrix {
-	type Output = Matrix;
-	fn sub(self, rhs: Matrix) -> Matrix {
+impl<T: Scalar> Sub<Mat4<T>> for Mat4<T> {
+	type Output = Mat4<T>;
+	fn sub(self, rhs: Mat4<T>) -> Mat4<T> {
 		let mut out = self;
 		for i in 0..4 {
 			for j in 0..4 {
@@ -240,39 +658,39 @@ impl Sub<Matrix> for Matrix {
 	}
 }
 
-impl std::iter::Sum for Matrix {
+impl<T: Scalar> std::iter::Sum for Mat4<T> {
 	fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
-		iter.fold(Matrix::new(), |a, b| a + b)
+		iter.fold(Mat4::new(), |a, b| a + b)
 	}
 }
 
-impl Index<usize> for Matrix {
-	type Output = [f32; 4];
-	fn index(&self, index: usize) -> &[f32; 4] {
+impl<T: Scalar> Index<usize> for Mat4<T> {
+	type Output = [T; 4];
+	fn index(&self, index: usize) -> &[T; 4] {
 		&self.data[index]
 	}
 }
-impl IndexMut<usize> for Matrix {
-	fn index_mut(&mut self, index: usize) -> &mut [f32; 4] {
+impl<T: Scalar> IndexMut<usize> for Mat4<T> {
+	fn index_mut(&mut self, index: usize) -> &mut [T; 4] {
 		&mut self.data[index]
 	}
 }
 
-impl Deref for Matrix {
-	type Target = [[f32; 4]; 4];
-	fn deref(&self) -> &[[f32; 4]; 4] {
+impl<T: Scalar> Deref for Mat4<T> {
+	type Target = [[T; 4]; 4];
+	fn deref(&self) -> &[[T; 4]; 4] {
 		&self.data
 	}
 }
-impl DerefMut for Matrix {
-	fn deref_mut(&mut self) -> &mut [[f32; 4]; 4] {
+impl<T: Scalar> DerefMut for Mat4<T> {
+	fn deref_mut(&mut self) -> &mut [[T; 4]; 4] {
 		&mut self.data
 	}
 }
 
 use std::fmt::{Display, Formatter, Result};
 
-impl Display for Matrix {
+impl<T: Scalar> Display for Mat4<T> {
 	fn fmt(&self, f: &mut Formatter) -> Result {
 		write!(
 			f,
@@ -281,3 +699,271 @@ impl Display for Matrix {
 		)
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use clip_space::{DepthRange, Handedness};
+	use quaternion::Quaternion;
+	use vector::Vector;
+
+	fn assert_mat_eq(a: Matrix, b: Matrix) {
+		for y in 0..4 {
+			for x in 0..4 {
+				assert!((a[y][x] - b[y][x]).abs() <= 1e-5, "\n{}\n!=\n{}", a, b);
+			}
+		}
+	}
+
+	#[test]
+	fn matrix_determinant() {
+		let mut m = Matrix::identity();
+		for i in 0..4 {
+			m[i][i] = i as f32 + 2.0;
+		}
+		assert!((m.determinant() - 120.0).abs() <= f32::EPSILON);
+		assert!((Matrix::rot_y(0.7).determinant() - 1.0).abs() <= 1e-6);
+	}
+
+	#[test]
+	fn matrix_inverse() {
+		let m = Matrix {
+			data: [
+				[1.0, 1.0, 1.0, -1.0],
+				[1.0, 1.0, -1.0, 1.0],
+				[1.0, -1.0, 1.0, 1.0],
+				[-1.0, 1.0, 1.0, 1.0],
+			],
+		};
+		assert!((m.determinant() + 16.0).abs() <= f32::EPSILON);
+		assert_mat_eq(m.adjugate(), m * -4.0);
+		assert_mat_eq(m.try_inverse().unwrap(), m * 0.25);
+		assert_mat_eq(m * m.try_inverse().unwrap(), Matrix::identity());
+	}
+
+	#[test]
+	fn matrix_inverse_affine() {
+		let delta = Vector::from((1.0, -2.0, 3.0));
+		let t = Matrix::translate(delta);
+		assert_mat_eq(t.try_inverse().unwrap(), Matrix::translate(-delta));
+
+		let r = Matrix::rot_z(0.3) * Matrix::rot_x(1.2);
+		assert_mat_eq(r.try_inverse().unwrap(), r.transposed());
+
+		let mut m = t * r;
+		m[1][1] *= 3.0;
+		let inverse = m.try_inverse_affine().unwrap();
+		// compare against the general inverse, since try_inverse would take the affine path as well
+		assert_mat_eq(inverse, m.adjugate() * (1.0 / m.determinant()));
+		assert_mat_eq(m * inverse, Matrix::identity());
+		m[3][0] = 0.5;
+		assert!(!m.is_affine());
+		assert_mat_eq(m * m.try_inverse().unwrap(), Matrix::identity());
+	}
+
+	#[test]
+	fn matrix_inverse_singular() {
+		let mut m = Matrix::identity();
+		m[2] = m[1];
+		assert!(m.try_inverse().is_none());
+		m[3][2] = 1.0;
+		assert!(m.try_inverse().is_none());
+	}
+
+	#[test]
+	fn matrix_rotation() {
+		let x = Vector::from((2.0, 0.0, 0.0));
+		assert_mat_eq(Matrix::rotation(x, 0.8), Matrix::rot_x(0.8));
+		assert_mat_eq(Matrix::rotation(Vector::from((0.0, -1.0, 0.0)), 0.8), Matrix::rot_y(-0.8));
+
+		let axis = Vector::from((1.0, 1.0, 1.0));
+		let m = Matrix::rotation(axis, 2.0 * std::f32::consts::PI / 3.0);
+		let v = m * Vector::from((1.0, 0.0, 0.0));
+		assert!((v - Vector::from((0.0, 1.0, 0.0))).length() <= 1e-6);
+		assert!(((m * axis) - axis).length() <= 1e-6);
+	}
+
+	#[test]
+	fn matrix_euler_roundtrip() {
+		let angles = [(0.3, 0.5, -2.0), (-2.9, 1.2, 0.1), (1.0, 2.5, 3.0), (0.7, -0.4, -1.6)];
+		for &order in EulerOrder::ALL.iter() {
+			for &(a, b, c) in angles.iter() {
+				let m = Matrix::from_euler(order, a, b, c);
+				let (a2, b2, c2) = m.to_euler(order);
+				assert_mat_eq(Matrix::from_euler(order, a2, b2, c2), m);
+				if order.is_proper() {
+					assert!((0.0..=std::f32::consts::PI).contains(&b2));
+				} else {
+					assert!(b2.abs() <= std::f32::consts::FRAC_PI_2);
+				}
+			}
+		}
+		let (a, b, c) = Matrix::from_euler(EulerOrder::ZXY, 0.3, 0.5, -0.2).to_euler(EulerOrder::ZXY);
+		assert!((a - 0.3).abs() <= 1e-6 && (b - 0.5).abs() <= 1e-6 && (c + 0.2).abs() <= 1e-6);
+	}
+
+	#[test]
+	fn matrix_euler_gimbal_lock() {
+		use std::f32::consts::{FRAC_PI_2, PI};
+		let cases = [(EulerOrder::XYZ, FRAC_PI_2), (EulerOrder::ZYX, -FRAC_PI_2), (EulerOrder::YXY, 0.0), (EulerOrder::ZYZ, PI)];
+		for &(order, b) in cases.iter() {
+			let m = Matrix::from_euler(order, 0.4, b, 0.9);
+			let (a2, b2, c2) = m.to_euler(order);
+			assert_eq!(c2, 0.0);
+			assert!((b2.abs() - b.abs()).abs() <= 1e-3);
+			assert_mat_eq(Matrix::from_euler(order, a2, b2, c2), m);
+		}
+	}
+
+	#[test]
+	fn matrix_scale_shear() {
+		let v = Vector::from((1.0, 2.0, 3.0));
+		assert_eq!(Matrix::scale(Vector::from((2.0, -1.0, 0.5))) * v, Vector::from((2.0, -2.0, 1.5)));
+		assert_eq!(Matrix::scale_uniform(3.0) * v, v * 3.0);
+		assert_eq!(Matrix::shear_x(1.0, 2.0) * v, Vector::from((9.0, 2.0, 3.0)));
+		assert_eq!(Matrix::shear_y(1.0, 2.0) * v, Vector::from((1.0, 9.0, 3.0)));
+		assert_eq!(Matrix::shear_z(1.0, 2.0) * v, Vector::from((1.0, 2.0, 8.0)));
+	}
+
+	#[test]
+	fn matrix_trs() {
+		let t = Vector::from((-1.0, 5.0, 0.5));
+		let r = Quaternion::from_axis_angle(Vector::from((1.0, 2.0, 0.0)), 1.3);
+		let s = Vector::from((2.0, 0.5, 3.0));
+		let m = Matrix::from_trs(t, r, s);
+		assert_mat_eq(m, Matrix::translate(t) * Matrix::from(r) * Matrix::scale(s));
+
+		let (t2, r2, s2) = m.decompose().unwrap();
+		assert!((t2 - t).length() <= 1e-6);
+		assert!((s2 - s).length() <= 1e-5);
+		assert_mat_eq(Matrix::from_trs(t2, r2, s2), m);
+	}
+
+	#[test]
+	fn matrix_decompose_negative_scale() {
+		let r = Quaternion::from_euler(0.5, -0.2, 2.0);
+		let m = Matrix::from_trs(Vector::new(), r, Vector::from((1.0, -2.0, 3.0)));
+		let (_, r2, s2) = m.decompose().unwrap();
+		assert!(s2.x < 0.0 && s2.y > 0.0 && s2.z > 0.0);
+		assert!((s2.length() - 14f32.sqrt()).abs() <= 1e-5);
+		assert_mat_eq(Matrix::from_trs(Vector::new(), r2, s2), m);
+
+		assert!(Matrix::scale(Vector::from((1.0, 0.0, 1.0))).decompose().is_none());
+		assert!(Matrix::projection((4, 3), 1.0, 0.1, 10.0).decompose().is_none());
+	}
+
+	#[test]
+	fn matrix_transform() {
+		let m = Matrix::translate(Vector::from((1.0, 2.0, 3.0))) * Matrix::rot_z(std::f32::consts::FRAC_PI_2);
+		let v = Vector::from((1.0, 0.0, 0.0));
+		assert!((m.transform_point(v) - Vector::from((1.0, 3.0, 3.0))).length() <= 1e-6);
+		assert!((m.transform_vector(v) - Vector::from((0.0, 1.0, 0.0))).length() <= 1e-6);
+		assert_eq!(m.transform_point(v), m * v);
+
+		let p = Matrix::projection((4, 4), 1.0, 0.1, 10.0);
+		assert_eq!(p.transform_homogeneous(Vector::from((0.0, 0.0, 5.0)), 1.0).w, 5.0);
+
+		// a point that is projected to w = 0 does not produce NaNs
+		let mut p = Matrix::identity();
+		p[3] = [0.0, 0.0, 1.0, 0.0];
+		assert_eq!(p.transform_point(Vector::from((1.0, 2.0, 0.0))), Vector::from((1.0, 2.0, 0.0)));
+	}
+
+	#[test]
+	fn matrix_transform_normal() {
+		// a plane with the normal (1, 1, 0) stretched along x
+		let m = Matrix::scale(Vector::from((2.0, 1.0, 1.0)));
+		let tangent = Vector::from((1.0, -1.0, 0.0));
+		let normal = Vector::from((1.0, 1.0, 0.0));
+		let n = m.transform_normal(normal);
+		assert!((m.transform_vector(tangent) * n).abs() <= 1e-6);
+		assert!((n - Vector::from((0.5, 1.0, 0.0))).length() <= 1e-6);
+
+		let shear = Matrix::shear_x(0.5, 1.5) * Matrix::translate(Vector::from((4.0, 0.0, 0.0)));
+		let n = shear.transform_normal(normal);
+		assert!((shear.transform_vector(tangent) * n).abs() <= 1e-6);
+		let inv_t = shear.try_inverse().unwrap().transposed();
+		assert!((inv_t.transform_vector(normal) - n).length() <= 1e-6);
+		assert!((Mat3::from(inv_t) * normal - shear.normal_matrix() * normal).length() <= 1e-6);
+
+		let flat = Matrix::scale(Vector::from((1.0, 1.0, 0.0)));
+		let n = flat.transform_normal(Vector::from((0.0, 0.0, 1.0)));
+		assert!(n.length() > 0.0 && n.x == 0.0 && n.y == 0.0);
+	}
+
+	const CLIP_SPACES: [ClipSpace; 6] = [
+		ClipSpace::OPENGL,
+		ClipSpace::VULKAN,
+		ClipSpace::DIRECT3D,
+		ClipSpace {
+			depth: DepthRange::NegativeOneToOne,
+			handedness: Handedness::Left,
+			reverse_z: false,
+		},
+		ClipSpace::VULKAN.reversed(),
+		ClipSpace::DIRECT3D.reversed(),
+	];
+
+	#[test]
+	fn matrix_look_at() {
+		let position = Vector::from((1.0, 2.0, 3.0));
+		let m = Matrix::look_at(position, Vector::from((1.0, 2.0, -7.0)), Vector::from((0.0, 1.0, 0.0)));
+		assert_eq!(m * position, Vector::new());
+		assert_eq!(m * Vector::from((1.0, 2.0, -1.0)), Vector::from((0.0, 0.0, 4.0)));
+		assert_eq!(m * Vector::from((1.0, 5.0, 3.0)), Vector::from((0.0, 3.0, 0.0)));
+		// looking along -z, +x of the world is to the left
+		assert_eq!(m * Vector::from((2.0, 2.0, 3.0)), Vector::from((-1.0, 0.0, 0.0)));
+		assert!((m.determinant() - 1.0).abs() <= 1e-6);
+	}
+
+	#[test]
+	fn matrix_perspective() {
+		let (fov, aspect, near, far) = (1.2f32, 1.5, 0.5, 20.0);
+		let (half_h, half_w) = ((fov / 2.0).tan(), (fov / 2.0).tan() * aspect);
+		for &clip in CLIP_SPACES.iter() {
+			let (a, b) = clip.depth_bounds();
+			let (a, b) = (a as f32, b as f32);
+			let z = clip.forward_z() as f32;
+			let m = Matrix::perspective(fov, aspect, near, far, clip);
+			let p = m * Vector::from((half_w * near, half_h * near, z * near));
+			assert!((p - Vector::from((1.0, 1.0, a))).length() <= 1e-5, "{:?}: {}", clip, p);
+			let p = m * Vector::from((-half_w * far, -half_h * far, z * far));
+			assert!((p - Vector::from((-1.0, -1.0, b))).length() <= 1e-5, "{:?}: {}", clip, p);
+			// a point behind the camera has a negative w
+			assert!(m.transform_homogeneous(Vector::from((0.0, 0.0, -z)), 1.0).w < 0.0);
+
+			let m = Matrix::perspective_infinite(fov, aspect, near, clip);
+			let p = m * Vector::from((0.0, half_h * near, z * near));
+			assert!((p - Vector::from((0.0, 1.0, a))).length() <= 1e-5, "{:?}: {}", clip, p);
+			let p = m * Vector::from((0.0, 0.0, z * 1e7));
+			assert!((p.z - b).abs() <= 1e-5, "{:?}: {}", clip, p);
+		}
+	}
+
+	#[test]
+	fn matrix_perspective_reverse_z() {
+		let m = Matrix::perspective(1.0, 1.0, 1.0, 100.0, ClipSpace::VULKAN.reversed());
+		let depth = |d: f32| (m * Vector::from((0.0, 0.0, -d))).z;
+		assert!((depth(1.0) - 1.0).abs() <= 1e-6);
+		assert!(depth(100.0).abs() <= 1e-6);
+		assert!(depth(2.0) > depth(50.0));
+	}
+
+	#[test]
+	fn matrix_orthographic() {
+		for &clip in CLIP_SPACES.iter() {
+			let (a, b) = clip.depth_bounds();
+			let (a, b) = (a as f32, b as f32);
+			let z = clip.forward_z() as f32;
+			let m = Matrix::orthographic_with(-4.0, 2.0, -1.0, 3.0, 1.0, 9.0, clip);
+			let p = m * Vector::from((-4.0, -1.0, z * 1.0));
+			assert!((p - Vector::from((-1.0, -1.0, a))).length() <= 1e-6, "{:?}: {}", clip, p);
+			let p = m * Vector::from((2.0, 3.0, z * 9.0));
+			assert!((p - Vector::from((1.0, 1.0, b))).length() <= 1e-6, "{:?}: {}", clip, p);
+			let p = m * Vector::from((-1.0, 1.0, z * 5.0));
+			assert!((p - Vector::from((0.0, 0.0, (a + b) / 2.0))).length() <= 1e-6, "{:?}: {}", clip, p);
+		}
+		let gl = Matrix::orthographic(-1.0, 1.0, -1.0, 1.0, 0.0, 2.0);
+		assert_eq!(gl * Vector::from((0.0, 0.0, -2.0)), Vector::from((0.0, 0.0, 1.0)));
+	}
+}
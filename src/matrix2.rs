@@ -0,0 +1,280 @@
+#![allow(clippy::needless_range_loop, clippy::new_without_default)]
+
+use matrix::Mat4;
+use matrix3::Mat3;
+use vector2::Vec2;
+use Scalar;
+
+/// A 2x2 Matrix for calculating with 2D Vectors of the Scalar Type `T`
+///
+/// Most code should use the [`Matrix2`](type.Matrix2.html) (`f32`) or [`DMatrix2`](type.DMatrix2.html) (`f64`) aliases.
+#[derive(Copy, Clone, Debug)]
+pub struct Mat2<T: Scalar> {
+	/// The internal data of the Matrix
+	pub data: [[T; 2]; 2],
+}
+
+/// A 2x2 Matrix with `f32` entries
+pub type Matrix2 = Mat2<f32>;
+/// A 2x2 Matrix with `f64` entries
+pub type DMatrix2 = Mat2<f64>;
+
+impl<T: Scalar> Mat2<T> {
+	/// Creates a new Matrix with all entries set to 0
+	///
+	/// ```text
+	/// [0  0]
+	/// [0  0]
+	/// ```
+	pub fn new() -> Mat2<T> {
+		Mat2 {
+			data: [[T::ZERO; 2]; 2],
+		}
+	}
+	/// Creates an Identity Matrix with the diagonal set to 1 and everything else set to 0
+	///
+	/// ```text
+	/// [1  0]
+	/// [0  1]
+	/// ```
+	pub fn identity() -> Mat2<T> {
+		let mut mat = Mat2::new();
+		for i in 0..2 {
+			mat[i][i] = T::ONE;
+		}
+		mat
+	}
+	/// Returns a Matrix created from Transposing this Matrix
+	///
+	/// A Transposed Matrix is mirrored along the diagonal, so that rows and columns are swapped
+	pub fn transposed(&self) -> Mat2<T> {
+		let mut mat = Mat2::new();
+		for y in 0..2 {
+			for x in 0..2 {
+				mat[x][y] = self[y][x];
+			}
+		}
+		mat
+	}
+	/// Creates a Rotation Matrix for rotating counter-clockwise by `radians`
+	pub fn rotation(radians: T) -> Mat2<T> {
+		let (s, c) = radians.sin_cos();
+		Mat2 {
+			data: [[c, -s], [s, c]],
+		}
+	}
+	/// Creates a Scaling Matrix that scales each Axis by the corresponding Component of `factor`
+	pub fn scale(factor: Vec2<T>) -> Mat2<T> {
+		Mat2 {
+			data: [[factor.x, T::ZERO], [T::ZERO, factor.y]],
+		}
+	}
+	/// Calculates the [determinant](https://en.wikipedia.org/wiki/Determinant) of the Matrix
+	pub fn determinant(&self) -> T {
+		self[0][0] * self[1][1] - self[0][1] * self[1][0]
+	}
+	/// Calculates the inverse of the Matrix
+	///
+	/// returns None if the Matrix is singular (the determinant is 0 or the inverse is not finite).
+	pub fn try_inverse(&self) -> Option<Mat2<T>> {
+		let det = self.determinant();
+		if det == T::ZERO {
+			return None;
+		}
+		let adjugate = Mat2 {
+			data: [[self[1][1], -self[0][1]], [-self[1][0], self[0][0]]],
+		};
+		Some(adjugate * (T::ONE / det)).filter(Mat2::is_finite)
+	}
+	/// Checks if all entries of the Matrix are finite
+	pub fn is_finite(&self) -> bool {
+		self.iter().all(|row| row.iter().all(|v| v.is_finite()))
+	}
+}
+
+impl<T: Scalar> From<Mat4<T>> for Mat2<T> {
+	/// Takes the upper-left 2x2 block of the Matrix
+	fn from(src: Mat4<T>) -> Mat2<T> {
+		Mat2 {
+			data: [[src[0][0], src[0][1]], [src[1][0], src[1][1]]],
+		}
+	}
+}
+impl<T: Scalar> From<Mat2<T>> for Mat4<T> {
+	/// Creates a Matrix with `src` as the upper-left 2x2 block and the rest taken from the Identity Matrix
+	fn from(src: Mat2<T>) -> Mat4<T> {
+		let mut mat = Mat4::identity();
+		for y in 0..2 {
+			mat[y][0..2].copy_from_slice(&src[y]);
+		}
+		mat
+	}
+}
+impl<T: Scalar> From<Mat3<T>> for Mat2<T> {
+	/// Takes the upper-left 2x2 block of the Matrix
+	fn from(src: Mat3<T>) -> Mat2<T> {
+		Mat2 {
+			data: [[src[0][0], src[0][1]], [src[1][0], src[1][1]]],
+		}
+	}
+}
+impl<T: Scalar> From<Mat2<T>> for Mat3<T> {
+	/// Creates a Matrix with `src` as the upper-left 2x2 block and the rest taken from the Identity Matrix
+	fn from(src: Mat2<T>) -> Mat3<T> {
+		let mut mat = Mat3::identity();
+		for y in 0..2 {
+			mat[y][0..2].copy_from_slice(&src[y]);
+		}
+		mat
+	}
+}
+
+use std::ops::*;
+
+impl<T: Scalar> Mul for Mat2<T> {
+	type Output = Mat2<T>;
+	fn mul(self, rhs: Self) -> Mat2<T> {
+		let mut ret = Mat2::new();
+		for y in 0..2 {
+			for x in 0..2 {
+				let mut sum = T::ZERO;
+				for i in 0..2 {
+					sum += self[y][i] * rhs[i][x];
+				}
+				ret[y][x] = sum;
+			}
+		}
+		ret
+	}
+}
+impl<T: Scalar> MulAssign for Mat2<T> {
+	fn mul_assign(&mut self, rhs: Self) {
+		*self = *self * rhs;
+	}
+}
+
+impl<T: Scalar> Mul<Vec2<T>> for Mat2<T> {
+	type Output = Vec2<T>;
+	fn mul(self, rhs: Vec2<T>) -> Vec2<T> {
+		let mut out = Vec2::new();
+		for i in 0..2 {
+			for j in 0..2 {
+				out[i] += self[i][j] * rhs[j];
+			}
+		}
+		out
+	}
+}
+
+impl<T: Scalar> Mul<T> for Mat2<T> {
+	type Output = Mat2<T>;
+	fn mul(self, rhs: T) -> Mat2<T> {
+		let mut out = self;
+		for i in 0..2 {
+			for j in 0..2 {
+				out[i][j] *= rhs;
+			}
+		}
+		out
+	}
+}
+
+impl<T: Scalar> Add<Mat2<T>> for Mat2<T> {
+	type Output = Mat2<T>;
+	fn add(self, rhs: Mat2<T>) -> Mat2<T> {
+		let mut out = self;
+		for i in 0..2 {
+			for j in 0..2 {
+				out[i][j] += rhs[i][j];
+			}
+		}
+		out
+	}
+}
+
+impl<T: Scalar> Sub<Mat2<T>> for Mat2<T> {
+	type Output = Mat2<T>;
+	fn sub(self, rhs: Mat2<T>) -> Mat2<T> {
+		let mut out = self;
+		for i in 0..2 {
+			for j in 0..2 {
+				out[i][j] -= rhs[i][j];
+			}
+		}
+		out
+	}
+}
+
+impl<T: Scalar> std::iter::Sum for Mat2<T> {
+	fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+		iter.fold(Mat2::new(), |a, b| a + b)
+	}
+}
+
+impl<T: Scalar> Index<usize> for Mat2<T> {
+	type Output = [T; 2];
+	fn index(&self, index: usize) -> &[T; 2] {
+		&self.data[index]
+	}
+}
+impl<T: Scalar> IndexMut<usize> for Mat2<T> {
+	fn index_mut(&mut self, index: usize) -> &mut [T; 2] {
+		&mut self.data[index]
+	}
+}
+
+impl<T: Scalar> Deref for Mat2<T> {
+	type Target = [[T; 2]; 2];
+	fn deref(&self) -> &[[T; 2]; 2] {
+		&self.data
+	}
+}
+impl<T: Scalar> DerefMut for Mat2<T> {
+	fn deref_mut(&mut self) -> &mut [[T; 2]; 2] {
+		&mut self.data
+	}
+}
+
+use std::fmt::{Display, Formatter, Result};
+
+impl<T: Scalar> Display for Mat2<T> {
+	fn fmt(&self, f: &mut Formatter) -> Result {
+		write!(
+			f,
+			"{:?}\n{:?}",
+			self.data[0], self.data[1],
+		)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use vector2::Vector2;
+
+	#[test]
+	fn matrix2_inverse() {
+		let m = Matrix2 {
+			data: [[4.0, 7.0], [2.0, 6.0]],
+		};
+		assert!((m.determinant() - 10.0).abs() <= f32::EPSILON);
+		let inv = m.try_inverse().unwrap();
+		let v = Vector2::from((1.0, 2.0));
+		assert!((inv * (m * v) - v).length() <= 1e-6);
+		assert!((inv * Vector2::from((1.0, 0.0)) - Vector2::from((0.6, -0.2))).length() <= 1e-6);
+		assert!(Matrix2::scale(Vector2::from((1.0, 0.0))).try_inverse().is_none());
+	}
+
+	#[test]
+	fn matrix2_block() {
+		let r = Matrix2::rotation(std::f32::consts::FRAC_PI_2);
+		assert!((r * Vector2::from((1.0, 0.0)) - Vector2::from((0.0, 1.0))).length() <= 1e-6);
+		let m4 = Mat4::from(r);
+		let v = Vector2::from((2.0, 3.0)).extend(4.0);
+		assert_eq!((m4 * v).truncate(), r * v.truncate());
+		assert_eq!((m4 * v).z, 4.0);
+		let m3 = Mat3::from(r);
+		assert_eq!(Matrix2::from(m3) * v.truncate(), r * v.truncate());
+		assert_eq!(Matrix2::from(Mat4::rot_z(0.3))[1], Matrix2::rotation(0.3)[1]);
+	}
+}
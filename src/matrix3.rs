@@ -0,0 +1,270 @@
+#![allow(clippy::needless_range_loop, clippy::new_without_default)]
+
+use matrix::Mat4;
+use vector::Vec3;
+use Scalar;
+
+/// A 3x3 Matrix for calculating with 3D Vectors of the Scalar Type `T`
+///
+/// Most code should use the [`Matrix3`](type.Matrix3.html) (`f32`) or [`DMatrix3`](type.DMatrix3.html) (`f64`) aliases.
+#[derive(Copy, Clone, Debug)]
+pub struct Mat3<T: Scalar> {
+	/// The internal data of the Matrix
+	pub data: [[T; 3]; 3],
+}
+
+/// A 3x3 Matrix with `f32` entries
+pub type Matrix3 = Mat3<f32>;
+/// A 3x3 Matrix with `f64` entries
+pub type DMatrix3 = Mat3<f64>;
+
+impl<T: Scalar> Mat3<T> {
+	/// Creates a new Matrix with all entries set to 0
+	///
+	/// ```text
+	/// [0  0  0]
+	/// [0  0  0]
+	/// [0  0  0]
+	/// ```
+	pub fn new() -> Mat3<T> {
+		Mat3 {
+			data: [[T::ZERO; 3]; 3],
+		}
+	}
+	/// Creates an Identity Matrix with the diagonal set to 1 and everything else set to 0
+	///
+	/// ```text
+	/// [1  0  0]
+	/// [0  1  0]
+	/// [0  0  1]
+	/// ```
+	pub fn identity() -> Mat3<T> {
+		let mut mat = Mat3::new();
+		for i in 0..3 {
+			mat[i][i] = T::ONE;
+		}
+		mat
+	}
+	/// Returns a Matrix created from Transposing this Matrix
+	///
+	/// A Transposed Matrix is mirrored along the diagonal, so that rows and columns are swapped
+	pub fn transposed(&self) -> Mat3<T> {
+		let mut mat = Mat3::new();
+		for y in 0..3 {
+			for x in 0..3 {
+				mat[x][y] = self[y][x];
+			}
+		}
+		mat
+	}
+	/// Calculates the cofactor of the entry at `row` and `col`
+	pub fn cofactor(&self, row: usize, col: usize) -> T {
+		let (y1, y2) = ((row + 1) % 3, (row + 2) % 3);
+		let (x1, x2) = ((col + 1) % 3, (col + 2) % 3);
+		// cyclic indices already include the sign of the cofactor
+		self[y1][x1] * self[y2][x2] - self[y1][x2] * self[y2][x1]
+	}
+	/// Calculates the [determinant](https://en.wikipedia.org/wiki/Determinant) of the Matrix
+	pub fn determinant(&self) -> T {
+		(0..3).map(|x| self[0][x] * self.cofactor(0, x)).sum()
+	}
+	/// Returns the [adjugate](https://en.wikipedia.org/wiki/Adjugate_matrix) of the Matrix
+	///
+	/// The adjugate is the transposed Matrix of cofactors. It is the inverse multiplied by the determinant.
+	pub fn adjugate(&self) -> Mat3<T> {
+		let mut mat = Mat3::new();
+		for y in 0..3 {
+			for x in 0..3 {
+				mat[x][y] = self.cofactor(y, x);
+			}
+		}
+		mat
+	}
+	/// Calculates the inverse of the Matrix
+	///
+	/// returns None if the Matrix is singular (the determinant is 0 or the inverse is not finite).
+	pub fn try_inverse(&self) -> Option<Mat3<T>> {
+		let det = self.determinant();
+		if det == T::ZERO {
+			return None;
+		}
+		Some(self.adjugate() * (T::ONE / det)).filter(Mat3::is_finite)
+	}
+	/// Checks if all entries of the Matrix are finite
+	pub fn is_finite(&self) -> bool {
+		self.iter().all(|row| row.iter().all(|v| v.is_finite()))
+	}
+}
+
+impl<T: Scalar> From<Mat4<T>> for Mat3<T> {
+	/// Takes the upper-left 3x3 block of the Matrix
+	fn from(src: Mat4<T>) -> Mat3<T> {
+		let mut mat = Mat3::new();
+		for y in 0..3 {
+			mat[y].copy_from_slice(&src[y][0..3]);
+		}
+		mat
+	}
+}
+impl<T: Scalar> From<Mat3<T>> for Mat4<T> {
+	/// Creates a Matrix with `src` as the upper-left 3x3 block and the rest taken from the Identity Matrix
+	fn from(src: Mat3<T>) -> Mat4<T> {
+		let mut mat = Mat4::identity();
+		for y in 0..3 {
+			mat[y][0..3].copy_from_slice(&src[y]);
+		}
+		mat
+	}
+}
+
+use std::ops::*;
+
+impl<T: Scalar> Mul for Mat3<T> {
+	type Output = Mat3<T>;
+	fn mul(self, rhs: Self) -> Mat3<T> {
+		let mut ret = Mat3::new();
+		for y in 0..3 {
+			for x in 0..3 {
+				let mut sum = T::ZERO;
+				for i in 0..3 {
+					sum += self[y][i] * rhs[i][x];
+				}
+				ret[y][x] = sum;
+			}
+		}
+		ret
+	}
+}
+impl<T: Scalar> MulAssign for Mat3<T> {
+	fn mul_assign(&mut self, rhs: Self) {
+		*self = *self * rhs;
+	}
+}
+
+impl<T: Scalar> Mul<Vec3<T>> for Mat3<T> {
+	type Output = Vec3<T>;
+	fn mul(self, rhs: Vec3<T>) -> Vec3<T> {
+		let mut out = Vec3::new();
+		for i in 0..3 {
+			for j in 0..3 {
+				out[i] += self[i][j] * rhs[j];
+			}
+		}
+		out
+	}
+}
+
+impl<T: Scalar> Mul<T> for Mat3<T> {
+	type Output = Mat3<T>;
+	fn mul(self, rhs: T) -> Mat3<T> {
+		let mut out = self;
+		for i in 0..3 {
+			for j in 0..3 {
+				out[i][j] *= rhs;
+			}
+		}
+		out
+	}
+}
+
+impl<T: Scalar> Add<Mat3<T>> for Mat3<T> {
+	type Output = Mat3<T>;
+	fn add(self, rhs: Mat3<T>) -> Mat3<T> {
+		let mut out = self;
+		for i in 0..3 {
+			for j in 0..3 {
+				out[i][j] += rhs[i][j];
+			}
+		}
+		out
+	}
+}
+
+impl<T: Scalar> Sub<Mat3<T>> for Mat3<T> {
+	type Output = Mat3<T>;
+	fn sub(self, rhs: Mat3<T>) -> Mat3<T> {
+		let mut out = self;
+		for i in 0..3 {
+			for j in 0..3 {
+				out[i][j] -= rhs[i][j];
+			}
+		}
+		out
+	}
+}
+
+impl<T: Scalar> std::iter::Sum for Mat3<T> {
+	fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+		iter.fold(Mat3::new(), |a, b| a + b)
+	}
+}
+
+impl<T: Scalar> Index<usize> for Mat3<T> {
+	type Output = [T; 3];
+	fn index(&self, index: usize) -> &[T; 3] {
+		&self.data[index]
+	}
+}
+impl<T: Scalar> IndexMut<usize> for Mat3<T> {
+	fn index_mut(&mut self, index: usize) -> &mut [T; 3] {
+		&mut self.data[index]
+	}
+}
+
+impl<T: Scalar> Deref for Mat3<T> {
+	type Target = [[T; 3]; 3];
+	fn deref(&self) -> &[[T; 3]; 3] {
+		&self.data
+	}
+}
+impl<T: Scalar> DerefMut for Mat3<T> {
+	fn deref_mut(&mut self) -> &mut [[T; 3]; 3] {
+		&mut self.data
+	}
+}
+
+use std::fmt::{Display, Formatter, Result};
+
+impl<T: Scalar> Display for Mat3<T> {
+	fn fmt(&self, f: &mut Formatter) -> Result {
+		write!(
+			f,
+			"{:?}\n{:?}\n{:?}",
+			self.data[0], self.data[1], self.data[2],
+		)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use vector::Vector;
+
+	#[test]
+	fn matrix3_inverse() {
+		let m = Matrix3 {
+			data: [[2.0, 0.0, 1.0], [1.0, 1.0, 0.0], [0.0, 3.0, 1.0]],
+		};
+		assert!((m.determinant() - 5.0).abs() <= f32::EPSILON);
+		let inv = m.try_inverse().unwrap();
+		let v = Vector::from((1.0, -2.0, 0.5));
+		assert!((inv * (m * v) - v).length() <= 1e-6);
+		assert!((m.transposed() * v - Vector::from((0.0, -0.5, 1.5))).length() <= 1e-6);
+
+		let singular = Matrix3 {
+			data: [[1.0, 2.0, 3.0], [2.0, 4.0, 6.0], [0.0, 1.0, 1.0]],
+		};
+		assert!(singular.try_inverse().is_none());
+	}
+
+	#[test]
+	fn matrix3_block() {
+		let m4 = Mat4::rot_x(0.5) * Mat4::translate(Vector::from((1.0, 2.0, 3.0)));
+		let m3 = Matrix3::from(m4);
+		let v = Vector::from((4.0, 5.0, 6.0));
+		assert_eq!(m3 * v, m4.transform_vector(v));
+		let back = Mat4::from(m3);
+		assert_eq!(back.transform_point(v), m4.transform_vector(v));
+		assert_eq!(back[3], [0.0, 0.0, 0.0, 1.0]);
+	}
+}
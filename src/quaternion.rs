@@ -0,0 +1,374 @@
+use matrix::Mat4;
+use vector::Vec3;
+use Scalar;
+
+/// A [Quaternion](https://en.wikipedia.org/wiki/Quaternions_and_spatial_rotation) for representing Rotations with Components of the Scalar Type `T`
+///
+/// Most code should use the [`Quaternion`](type.Quaternion.html) (`f32`) or [`DQuaternion`](type.DQuaternion.html) (`f64`) aliases.
+///
+/// Only normalized Quaternions represent Rotations. All constructors in this Module return
+/// normalized Quaternions, but arithmetic on the Components may change that.
+#[derive(Clone, Copy, Debug)]
+pub struct Quat<T: Scalar> {
+	/// the x Component of the vector part
+	pub x: T,
+	/// the y Component of the vector part
+	pub y: T,
+	/// the z Component of the vector part
+	pub z: T,
+	/// the scalar part
+	pub w: T,
+}
+
+/// A Quaternion with `f32` Components
+pub type Quaternion = Quat<f32>;
+/// A Quaternion with `f64` Components
+pub type DQuaternion = Quat<f64>;
+
+impl<T: Scalar> Quat<T> {
+	/// Creates a Quaternion from its vector part and scalar part
+	pub fn from_parts(vector: Vec3<T>, w: T) -> Quat<T> {
+		Quat {
+			x: vector.x,
+			y: vector.y,
+			z: vector.z,
+			w,
+		}
+	}
+	/// Creates the Identity Quaternion, which represents no Rotation
+	pub fn identity() -> Quat<T> {
+		Quat::from_parts(Vec3::new(), T::ONE)
+	}
+	/// Creates a Quaternion for rotating around `axis` by `radians`
+	///
+	/// `axis` does not have to be normalized
+	///
+	/// ```
+	/// # use utils_3d::{Quaternion, Vector}; use std::f32::consts::PI;
+	/// let q = Quaternion::from_axis_angle(Vector::from((0.0, 0.0, 2.0)), PI / 2.0);
+	/// let v = q * Vector::from((1.0, 0.0, 0.0));
+	/// assert!((v - Vector::from((0.0, 1.0, 0.0))).length() <= 1e-6);
+	/// ```
+	pub fn from_axis_angle(axis: Vec3<T>, radians: T) -> Quat<T> {
+		let (s, c) = (radians / T::TWO).sin_cos();
+		Quat::from_parts(axis.norm() * s, c)
+	}
+	/// Creates a Quaternion from Euler angles in Radians
+	///
+	/// The Rotations are applied in the order x, y, z around the fixed Axes,
+	/// which is the same as `Matrix::rot_z(z) * Matrix::rot_y(y) * Matrix::rot_x(x)`
+	pub fn from_euler(x: T, y: T, z: T) -> Quat<T> {
+		let (sx, cx) = (x / T::TWO).sin_cos();
+		let (sy, cy) = (y / T::TWO).sin_cos();
+		let (sz, cz) = (z / T::TWO).sin_cos();
+		Quat {
+			x: sx * cy * cz - cx * sy * sz,
+			y: cx * sy * cz + sx * cy * sz,
+			z: cx * cy * sz - sx * sy * cz,
+			w: cx * cy * cz + sx * sy * sz,
+		}
+	}
+	/// Creates a Quaternion from the Rotation part of a Matrix
+	///
+	/// The upper-left 3x3 block of `mat` should be a pure Rotation (orthonormal with a determinant of 1).
+	pub fn from_matrix(mat: &Mat4<T>) -> Quat<T> {
+		let m = mat;
+		let quarter = T::ONE / (T::TWO * T::TWO);
+		let trace = m[0][0] + m[1][1] + m[2][2];
+		// pick the largest Component to divide by for numerical stability
+		let q = if trace > T::ZERO {
+			let s = (trace + T::ONE).sqrt() * T::TWO;
+			Quat {
+				x: (m[2][1] - m[1][2]) / s,
+				y: (m[0][2] - m[2][0]) / s,
+				z: (m[1][0] - m[0][1]) / s,
+				w: s * quarter,
+			}
+		} else if m[0][0] > m[1][1] && m[0][0] > m[2][2] {
+			let s = (T::ONE + m[0][0] - m[1][1] - m[2][2]).sqrt() * T::TWO;
+			Quat {
+				x: s * quarter,
+				y: (m[0][1] + m[1][0]) / s,
+				z: (m[0][2] + m[2][0]) / s,
+				w: (m[2][1] - m[1][2]) / s,
+			}
+		} else if m[1][1] > m[2][2] {
+			let s = (T::ONE + m[1][1] - m[0][0] - m[2][2]).sqrt() * T::TWO;
+			Quat {
+				x: (m[0][1] + m[1][0]) / s,
+				y: s * quarter,
+				z: (m[1][2] + m[2][1]) / s,
+				w: (m[0][2] - m[2][0]) / s,
+			}
+		} else {
+			let s = (T::ONE + m[2][2] - m[0][0] - m[1][1]).sqrt() * T::TWO;
+			Quat {
+				x: (m[0][2] + m[2][0]) / s,
+				y: (m[1][2] + m[2][1]) / s,
+				z: s * quarter,
+				w: (m[1][0] - m[0][1]) / s,
+			}
+		};
+		q.norm()
+	}
+	/// Creates a Rotation Matrix that rotates the same way as this Quaternion
+	pub fn to_matrix(self) -> Mat4<T> {
+		let Quat { x, y, z, w } = self;
+		let (one, two) = (T::ONE, T::TWO);
+		let mut mat = Mat4::identity();
+		mat.data[0] = [
+			one - two * (y * y + z * z),
+			two * (x * y - z * w),
+			two * (x * z + y * w),
+			T::ZERO,
+		];
+		mat.data[1] = [
+			two * (x * y + z * w),
+			one - two * (x * x + z * z),
+			two * (y * z - x * w),
+			T::ZERO,
+		];
+		mat.data[2] = [
+			two * (x * z - y * w),
+			two * (y * z + x * w),
+			one - two * (x * x + y * y),
+			T::ZERO,
+		];
+		mat
+	}
+	/// Returns the vector part (x, y, z) of the Quaternion
+	pub fn vector(self) -> Vec3<T> {
+		Vec3 {
+			x: self.x,
+			y: self.y,
+			z: self.z,
+		}
+	}
+	/// Calculates the dot product of two Quaternions
+	pub fn dot(self, rhs: Quat<T>) -> T {
+		self.x * rhs.x + self.y * rhs.y + self.z * rhs.z + self.w * rhs.w
+	}
+	/// Calculates the length of the Quaternion
+	pub fn length(self) -> T {
+		self.length_sq().sqrt()
+	}
+	/// Calculates the squared length of the Quaternion
+	pub fn length_sq(self) -> T {
+		self.dot(self)
+	}
+	/// Returns a normalized Quaternion with a length of 1
+	pub fn norm(self) -> Quat<T> {
+		self * (T::ONE / self.length())
+	}
+	/// Returns the conjugate of the Quaternion, which has the vector part negated
+	///
+	/// For normalized Quaternions this is the same as the [inverse](#method.inverse), but faster.
+	pub fn conjugate(self) -> Quat<T> {
+		Quat::from_parts(-self.vector(), self.w)
+	}
+	/// Returns the inverse of the Quaternion, which represents the opposite Rotation
+	pub fn inverse(self) -> Quat<T> {
+		self.conjugate() * (T::ONE / self.length_sq())
+	}
+	/// Rotates a Vector by this Quaternion
+	///
+	/// This is the same as `self * v`
+	pub fn rotate(self, v: Vec3<T>) -> Vec3<T> {
+		// optimized form of self * (v, 0) * self.conjugate()
+		let q = self.vector();
+		let t = q.cross(v) * T::TWO;
+		v + t * self.w + q.cross(t)
+	}
+	/// Normalized linear interpolation between `self` (`t = 0`) and `other` (`t = 1`)
+	///
+	/// This is faster than [slerp](#method.slerp), but does not have a constant angular velocity.
+	/// Always interpolates along the shortest path.
+	pub fn nlerp(self, other: Quat<T>, t: T) -> Quat<T> {
+		let other = if self.dot(other) < T::ZERO { -other } else { other };
+		(self * (T::ONE - t) + other * t).norm()
+	}
+	/// Spherical linear interpolation between `self` (`t = 0`) and `other` (`t = 1`)
+	///
+	/// Interpolates along the shortest path with a constant angular velocity.
+	///
+	/// ```
+	/// # use utils_3d::{Quaternion, Vector};
+	/// let axis = Vector::from((1.0, 0.0, 0.0));
+	/// let a = Quaternion::from_axis_angle(axis, 0.2);
+	/// let b = Quaternion::from_axis_angle(axis, 1.0);
+	/// assert_eq!(a.slerp(b, 0.5), Quaternion::from_axis_angle(axis, 0.6));
+	/// ```
+	pub fn slerp(self, other: Quat<T>, t: T) -> Quat<T> {
+		let mut cos = self.dot(other);
+		let mut other = other;
+		if cos < T::ZERO {
+			other = -other;
+			cos = -cos;
+		}
+		// sin(angle) approaches 0 for very close Quaternions
+		if cos > T::ONE - T::from_f64(1e-4) {
+			return self.nlerp(other, t);
+		}
+		let angle = cos.acos();
+		let sin = angle.sin();
+		let a = ((T::ONE - t) * angle).sin() / sin;
+		let b = (t * angle).sin() / sin;
+		(self * a + other * b).norm()
+	}
+}
+
+impl<T: Scalar> From<Quat<T>> for Mat4<T> {
+	fn from(src: Quat<T>) -> Mat4<T> {
+		src.to_matrix()
+	}
+}
+impl<T: Scalar> From<Mat4<T>> for Quat<T> {
+	fn from(src: Mat4<T>) -> Quat<T> {
+		Quat::from_matrix(&src)
+	}
+}
+
+use std::ops::*;
+
+impl<T: Scalar> Add for Quat<T> {
+	type Output = Quat<T>;
+	fn add(self, rhs: Quat<T>) -> Quat<T> {
+		Quat {
+			x: self.x + rhs.x,
+			y: self.y + rhs.y,
+			z: self.z + rhs.z,
+			w: self.w + rhs.w,
+		}
+	}
+}
+
+impl<T: Scalar> Mul for Quat<T> {
+	type Output = Quat<T>;
+	/// The [Hamilton product](https://en.wikipedia.org/wiki/Quaternion#Hamilton_product) of two Quaternions
+	///
+	/// The result rotates by `rhs` first and then by `self`
+	fn mul(self, rhs: Quat<T>) -> Quat<T> {
+		Quat {
+			x: self.w * rhs.x + self.x * rhs.w + self.y * rhs.z - self.z * rhs.y,
+			y: self.w * rhs.y - self.x * rhs.z + self.y * rhs.w + self.z * rhs.x,
+			z: self.w * rhs.z + self.x * rhs.y - self.y * rhs.x + self.z * rhs.w,
+			w: self.w * rhs.w - self.x * rhs.x - self.y * rhs.y - self.z * rhs.z,
+		}
+	}
+}
+impl<T: Scalar> MulAssign for Quat<T> {
+	fn mul_assign(&mut self, rhs: Quat<T>) {
+		*self = *self * rhs;
+	}
+}
+
+impl<T: Scalar> Mul<Vec3<T>> for Quat<T> {
+	type Output = Vec3<T>;
+	fn mul(self, rhs: Vec3<T>) -> Vec3<T> {
+		self.rotate(rhs)
+	}
+}
+
+impl<T: Scalar> Mul<T> for Quat<T> {
+	type Output = Quat<T>;
+	fn mul(self, rhs: T) -> Quat<T> {
+		Quat {
+			x: self.x * rhs,
+			y: self.y * rhs,
+			z: self.z * rhs,
+			w: self.w * rhs,
+		}
+	}
+}
+
+impl<T: Scalar> Neg for Quat<T> {
+	type Output = Quat<T>;
+	fn neg(self) -> Quat<T> {
+		self * -T::ONE
+	}
+}
+
+impl<T: Scalar> PartialEq for Quat<T> {
+	fn eq(&self, rhs: &Quat<T>) -> bool {
+		let epsilon = T::EPSILON;
+		(self.x - rhs.x).abs() <= epsilon
+			&& (self.y - rhs.y).abs() <= epsilon
+			&& (self.z - rhs.z).abs() <= epsilon
+			&& (self.w - rhs.w).abs() <= epsilon
+	}
+}
+
+use std::fmt::{Display, Formatter, Result};
+
+impl<T: Scalar> Display for Quat<T> {
+	fn fmt(&self, f: &mut Formatter) -> Result {
+		write!(f, "({}, {}, {}, {})", self.x, self.y, self.z, self.w)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use matrix::Matrix;
+	use vector::Vector;
+
+	fn assert_vec_eq(a: Vector, b: Vector) {
+		assert!((a - b).length() <= 1e-5, "{} != {}", a, b);
+	}
+
+	#[test]
+	fn quaternion_axis_angle() {
+		let v = Vector::from((1.0, 2.0, 3.0));
+		let x = Quaternion::from_axis_angle(Vector::from((1.0, 0.0, 0.0)), 0.5);
+		let y = Quaternion::from_axis_angle(Vector::from((0.0, 1.0, 0.0)), 0.5);
+		let z = Quaternion::from_axis_angle(Vector::from((0.0, 0.0, 1.0)), 0.5);
+		assert_vec_eq(x * v, Matrix::rot_x(0.5) * v);
+		assert_vec_eq(y * v, Matrix::rot_y(0.5) * v);
+		assert_vec_eq(z * v, Matrix::rot_z(0.5) * v);
+		assert_vec_eq((z * x) * v, Matrix::rot_z(0.5) * Matrix::rot_x(0.5) * v);
+	}
+
+	#[test]
+	fn quaternion_euler() {
+		let v = Vector::from((-1.0, 0.5, 2.0));
+		let q = Quaternion::from_euler(0.3, -1.1, 2.0);
+		let m = Matrix::rot_z(2.0) * Matrix::rot_y(-1.1) * Matrix::rot_x(0.3);
+		assert_vec_eq(q * v, m * v);
+	}
+
+	#[test]
+	fn quaternion_inverse() {
+		let q = Quaternion::from_euler(0.3, -1.1, 2.0) * 2.0;
+		assert_eq!(q * q.inverse(), Quaternion::identity());
+		let q = q.norm();
+		assert_eq!(q.inverse(), q.conjugate());
+		let v = Vector::from((3.0, 1.0, -2.0));
+		assert_vec_eq(q.conjugate() * (q * v), v);
+	}
+
+	#[test]
+	fn quaternion_matrix_roundtrip() {
+		let axes = [(1.0, 0.0, 0.0), (0.0, 1.0, 0.0), (0.0, 0.0, 1.0), (1.0, -2.0, 0.5)];
+		for &axis in axes.iter() {
+			for &angle in [0.0, 0.7, 2.5, 3.1, -1.9].iter() {
+				let q = Quaternion::from_axis_angle(Vector::from(axis), angle);
+				let back = Quaternion::from(Matrix::from(q));
+				// q and -q represent the same Rotation
+				assert!(back.dot(q).abs() >= 1.0 - 1e-6, "{} != {}", back, q);
+			}
+		}
+	}
+
+	#[test]
+	fn quaternion_interpolation() {
+		let axis = Vector::from((0.0, 1.0, 1.0));
+		let a = Quaternion::from_axis_angle(axis, 0.0);
+		let b = Quaternion::from_axis_angle(axis, 2.0);
+		let v = Vector::from((1.0, 0.0, 0.0));
+		let expected = Quaternion::from_axis_angle(axis, 0.5) * v;
+		assert_vec_eq(a.slerp(b, 0.25) * v, expected);
+		assert_vec_eq(a.slerp(-b, 0.25) * v, expected);
+		assert_vec_eq(a.nlerp(b, 0.5) * v, Quaternion::from_axis_angle(axis, 1.0) * v);
+		assert_eq!(a.slerp(a, 0.5), a);
+	}
+}
@@ -0,0 +1,354 @@
+use ray_tracing::{HitInfo, Ray, RayTarget};
+use shapes::{Aabb, Bounded};
+use vector::Vec3;
+use Scalar;
+
+/// The number of bins that the centroids are sorted into when searching for a split
+const BIN_COUNT: usize = 12;
+/// The largest number of primitives that may be put into a leaf, even if splitting is more expensive
+const MAX_LEAF_SIZE: usize = 8;
+/// The cost of testing a Ray against a node, relative to testing it against a primitive
+const TRAVERSAL_COST: f64 = 1.0;
+
+/// A node of a [Bvh](struct.Bvh.html), stored in depth-first order
+///
+/// The first child of an inner node directly follows the node itself
+#[derive(Clone, Debug)]
+struct BvhNode<T: Scalar> {
+	/// The bounds of all primitives below this node
+	bounds: Aabb<T>,
+	/// leaf: the position of the first primitive in `Bvh::indices`
+	///
+	/// inner node: the index of the second child
+	offset: usize,
+	/// The number of primitives in a leaf, or 0 for inner nodes
+	count: usize,
+	/// The Axis along which the children of an inner node were split
+	axis: usize,
+}
+
+/// A bounding volume hierarchy to accelerate Raycasting against many primitives
+///
+/// The hierarchy is built once using the surface area heuristic and stored as a flat list of
+/// nodes. Hits report the index of the primitive in the list that the Bvh was created from.
+#[derive(Clone, Debug)]
+pub struct Bvh<P, T: Scalar = f32> {
+	primitives: Vec<P>,
+	indices: Vec<usize>,
+	nodes: Vec<BvhNode<T>>,
+}
+
+impl<T: Scalar, P: Bounded<T>> Bvh<P, T> {
+	/// builds a new Bvh over the primitives
+	///
+	/// Primitives with empty bounds can never be hit and are dropped from the hierarchy
+	pub fn new(primitives: Vec<P>) -> Bvh<P, T> {
+		Bvh::with_bounds(primitives, Bounded::bounds)
+	}
+}
+
+impl<T: Scalar, P> Bvh<P, T> {
+	/// builds a new Bvh over primitives whose bounds are calculated by `bounds`
+	///
+	/// This allows the primitives to be lightweight handles, like indices into shared data.
+	/// Primitives with empty bounds can never be hit and are dropped from the hierarchy.
+	pub fn with_bounds<F: Fn(&P) -> Aabb<T>>(primitives: Vec<P>, bounds: F) -> Bvh<P, T> {
+		let bounds: Vec<Aabb<T>> = primitives.iter().map(bounds).collect();
+		let centroids: Vec<Vec3<T>> = bounds.iter().map(Aabb::center).collect();
+		let mut indices: Vec<usize> = (0..primitives.len()).filter(|&i| !bounds[i].is_empty()).collect();
+		let mut nodes = Vec::new();
+		if !indices.is_empty() {
+			let count = indices.len();
+			build(&mut nodes, &mut indices, 0, count, &bounds, &centroids);
+		}
+		Bvh {
+			primitives,
+			indices,
+			nodes,
+		}
+	}
+	/// Returns the primitives in the order that they were given to [new](#method.new)
+	pub fn primitives(&self) -> &[P] {
+		&self.primitives
+	}
+	/// Returns the primitives, destroying the hierarchy
+	pub fn into_primitives(self) -> Vec<P> {
+		self.primitives
+	}
+	/// Returns the number of nodes in the hierarchy
+	pub fn node_count(&self) -> usize {
+		self.nodes.len()
+	}
+	/// finds the closest hit of the Ray with any primitive, intersecting the primitives with `hit_info`
+	///
+	/// `hit_info` has to follow the rules of [RayTarget::hit_info](trait.RayTarget.html#tymethod.hit_info).
+	///
+	/// returns the index of the hit primitive together with the hit, or None if nothing is hit
+	pub fn nearest_hit_with<F: FnMut(&P, &Ray<T>) -> Option<HitInfo<T>>>(&self, ray: &Ray<T>, mut hit_info: F) -> Option<(usize, HitInfo<T>)> {
+		let mut ray = *ray;
+		let mut closest = None;
+		self.traverse(&ray.clone(), |index| {
+			if let Some(hit) = hit_info(&self.primitives[index], &ray) {
+				ray.t_max = hit.distance;
+				closest = Some((index, hit));
+			}
+			Some(ray.t_max)
+		});
+		closest
+	}
+	/// checks if the Ray hits any primitive, testing the primitives with `hits`
+	///
+	/// This stops at the first hit that is found.
+	pub fn any_hit_with<F: FnMut(&P, &Ray<T>) -> bool>(&self, ray: &Ray<T>, mut hits: F) -> bool {
+		let mut found = false;
+		self.traverse(ray, |index| {
+			if hits(&self.primitives[index], ray) {
+				found = true;
+				None
+			} else {
+				Some(ray.t_max)
+			}
+		});
+		found
+	}
+	/// visits all primitives in leaves that the Ray hits, nearer nodes first
+	///
+	/// `visit` returns the new `t_max` of the Ray, or None to stop the traversal
+	fn traverse<F: FnMut(usize) -> Option<T>>(&self, ray: &Ray<T>, mut visit: F) {
+		if self.nodes.is_empty() {
+			return;
+		}
+		let mut ray = *ray;
+		let mut stack = Vec::with_capacity(64);
+		stack.push(0);
+		while let Some(node_index) = stack.pop() {
+			let node = &self.nodes[node_index];
+			if node.bounds.intersect_ray(&ray).is_none() {
+				continue;
+			}
+			if node.count > 0 {
+				for &index in &self.indices[node.offset..node.offset + node.count] {
+					match visit(index) {
+						Some(t_max) => ray.t_max = t_max,
+						None => return,
+					}
+				}
+			} else if ray.direction[node.axis] < T::ZERO {
+				// the second child is on the positive side of the split and therefore closer
+				stack.push(node_index + 1);
+				stack.push(node.offset);
+			} else {
+				stack.push(node.offset);
+				stack.push(node_index + 1);
+			}
+		}
+	}
+}
+
+impl<T: Scalar, P: RayTarget<T>> Bvh<P, T> {
+	/// finds the closest hit of the Ray with any primitive
+	///
+	/// returns the index of the hit primitive together with the hit, or None if nothing is hit
+	pub fn nearest_hit(&self, ray: &Ray<T>) -> Option<(usize, HitInfo<T>)> {
+		self.nearest_hit_with(ray, P::hit_info)
+	}
+	/// checks if the Ray hits any primitive
+	///
+	/// This stops at the first hit that is found, which makes it faster than
+	/// [nearest_hit](#method.nearest_hit) for occlusion tests like shadow Rays.
+	pub fn any_hit(&self, ray: &Ray<T>) -> bool {
+		self.any_hit_with(ray, P::hits)
+	}
+}
+
+/// recursively builds the node for `indices[start..end]` and all its children
+fn build<T: Scalar>(nodes: &mut Vec<BvhNode<T>>, indices: &mut [usize], start: usize, end: usize, bounds: &[Aabb<T>], centroids: &[Vec3<T>]) {
+	let node_index = nodes.len();
+	let node_bounds = indices[start..end].iter().fold(Aabb::empty(), |acc, &i| acc.union(bounds[i]));
+	let count = end - start;
+	nodes.push(BvhNode {
+		bounds: node_bounds,
+		offset: start,
+		count,
+		axis: 0,
+	});
+	if count == 1 {
+		return;
+	}
+
+	let centroid_bounds = Aabb::from_points(indices[start..end].iter().map(|&i| centroids[i]));
+	let extents = centroid_bounds.extents();
+	let axis = if extents.x >= extents.y && extents.x >= extents.z {
+		0
+	} else if extents.y >= extents.z {
+		1
+	} else {
+		2
+	};
+	if extents[axis] <= T::ZERO {
+		// all centroids are at the same Point, so there is nothing to split
+		return;
+	}
+
+	let min = centroid_bounds.min[axis].to_f64();
+	let scale = BIN_COUNT as f64 / extents[axis].to_f64();
+	let bin_of = |i: usize| (((centroids[i][axis].to_f64() - min) * scale) as usize).min(BIN_COUNT - 1);
+
+	let mut bins = [(Aabb::empty(), 0); BIN_COUNT];
+	for &i in &indices[start..end] {
+		let bin = &mut bins[bin_of(i)];
+		bin.0 = bin.0.union(bounds[i]);
+		bin.1 += 1;
+	}
+
+	// cost of splitting after each bin, according to the surface area heuristic
+	let mut costs = [0.0; BIN_COUNT - 1];
+	let (mut left, mut left_count) = (Aabb::empty(), 0);
+	for split in 0..BIN_COUNT - 1 {
+		left = left.union(bins[split].0);
+		left_count += bins[split].1;
+		costs[split] = left.surface_area().to_f64() * left_count as f64;
+	}
+	let (mut right, mut right_count) = (Aabb::empty(), 0);
+	for split in (0..BIN_COUNT - 1).rev() {
+		right = right.union(bins[split + 1].0);
+		right_count += bins[split + 1].1;
+		costs[split] += right.surface_area().to_f64() * right_count as f64;
+	}
+	let (best_split, best_cost) = costs
+		.iter()
+		.cloned()
+		.enumerate()
+		.fold((0, f64::INFINITY), |best, (split, cost)| if cost < best.1 { (split, cost) } else { best });
+
+	let area = node_bounds.surface_area().to_f64();
+	let split_cost = TRAVERSAL_COST + best_cost / area;
+	if count <= MAX_LEAF_SIZE && split_cost >= count as f64 {
+		return;
+	}
+
+	// partition the primitives so that the ones left of the split come first
+	let mut mid = start;
+	for i in start..end {
+		if bin_of(indices[i]) <= best_split {
+			indices.swap(i, mid);
+			mid += 1;
+		}
+	}
+	if mid == start || mid == end {
+		mid = start + count / 2;
+	}
+
+	build(nodes, indices, start, mid, bounds, centroids);
+	let second = nodes.len();
+	build(nodes, indices, mid, end, bounds, centroids);
+	nodes[node_index] = BvhNode {
+		bounds: node_bounds,
+		offset: second,
+		count: 0,
+		axis,
+	};
+}
+
+impl<T: Scalar, P> Bounded<T> for Bvh<P, T> {
+	fn bounds(&self) -> Aabb<T> {
+		self.nodes.first().map(|node| node.bounds).unwrap_or_else(Aabb::empty)
+	}
+}
+
+impl<T: Scalar, P: RayTarget<T>> RayTarget<T> for Bvh<P, T> {
+	fn hit_info(&self, ray: &Ray<T>) -> Option<HitInfo<T>> {
+		self.nearest_hit(ray).map(|(_, hit)| hit)
+	}
+	fn hits(&self, ray: &Ray<T>) -> bool {
+		self.any_hit(ray)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use shapes::{Sphere, Triangle};
+	use vector::Vector;
+
+	/// a bumpy grid of Triangles in the xy Plane
+	fn grid(size: usize) -> Vec<Triangle> {
+		let point = |x: usize, y: usize| Vector::from((x as f32, y as f32, ((x * 7 + y * 3) % 5) as f32 * 0.2));
+		let mut triangles = vec![];
+		for x in 0..size {
+			for y in 0..size {
+				triangles.push(Triangle::new(point(x, y), point(x + 1, y), point(x + 1, y + 1)));
+				triangles.push(Triangle::new(point(x, y), point(x + 1, y + 1), point(x, y + 1)));
+			}
+		}
+		triangles
+	}
+
+	fn brute_force<P: RayTarget>(primitives: &[P], ray: &Ray) -> Option<(usize, f32)> {
+		primitives
+			.iter()
+			.enumerate()
+			.filter_map(|(i, p)| p.hit_info(ray).map(|hit| (i, hit.distance)))
+			.fold(None, |best, (i, d)| match best {
+				Some((_, best_d)) if best_d <= d => best,
+				_ => Some((i, d)),
+			})
+	}
+
+	#[test]
+	fn bvh_matches_brute_force() {
+		let triangles = grid(16);
+		let bvh = Bvh::new(triangles.clone());
+		assert_eq!(bvh.primitives().len(), 512);
+		assert!(bvh.node_count() > 1);
+		assert_eq!(bvh.bounds(), Aabb::from_triangles(&triangles));
+
+		for i in 0..400 {
+			let start = Vector::from(((i % 20) as f32 - 2.0, (i / 20) as f32 - 2.0, 5.0));
+			let direction = Vector::from(((i % 7) as f32 * 0.1 - 0.3, (i % 3) as f32 * 0.1, -1.0));
+			let ray = Ray::new(start, direction);
+			let expected = brute_force(&triangles, &ray);
+			let actual = bvh.nearest_hit(&ray).map(|(i, hit)| (i, hit.distance));
+			match (expected, actual) {
+				(Some((_, e)), Some((index, a))) => {
+					assert!((e - a).abs() <= 1e-5);
+					assert!((triangles[index].hit_info(&ray).unwrap().distance - a).abs() <= 1e-5);
+				}
+				(None, None) => {}
+				_ => panic!("{:?} != {:?} for {:?}", expected, actual, ray),
+			}
+			assert_eq!(bvh.any_hit(&ray), expected.is_some());
+		}
+	}
+
+	#[test]
+	fn bvh_spheres() {
+		let spheres: Vec<Sphere> = (0..50).map(|i| Sphere::new(Vector::from((i as f32 * 3.0, 0.0, 0.0)), 1.0)).collect();
+		let bvh = Bvh::new(spheres);
+		let ray = Ray::new(Vector::from((200.0, 0.0, 0.0)), Vector::from((-1.0, 0.0, 0.0)));
+		let (index, hit) = bvh.nearest_hit(&ray).unwrap();
+		assert_eq!(index, 49);
+		assert!((hit.distance - 52.0).abs() <= 1e-4);
+		assert_eq!(bvh.nearest_hit(&Ray::new(Vector::from((-10.0, 0.0, 0.0)), Vector::from((1.0, 0.0, 0.0)))).unwrap().0, 0);
+
+		// occlusion between two Points
+		assert!(bvh.hits(&Ray::segment(Vector::from((30.0, 5.0, 0.0)), Vector::from((30.0, -5.0, 0.0)))));
+		assert!(!bvh.hits(&Ray::segment(Vector::from((31.5, 5.0, 0.0)), Vector::from((31.5, -5.0, 0.0)))));
+		assert!(!bvh.hits(&Ray::segment(Vector::from((30.0, 5.0, 0.0)), Vector::from((30.0, 2.0, 0.0)))));
+	}
+
+	#[test]
+	fn bvh_degenerate() {
+		let empty: Bvh<Triangle> = Bvh::new(vec![]);
+		let ray = Ray::new(Vector::new(), Vector::from((0.0, 0.0, -1.0)));
+		assert!(empty.nearest_hit(&ray).is_none());
+		assert!(!empty.any_hit(&ray));
+		assert!(empty.bounds().is_empty());
+
+		// many primitives at the same place can't be split
+		let same = vec![Sphere::new(Vector::from((0.0, 0.0, -5.0)), 1.0); 100];
+		let bvh = Bvh::new(same);
+		assert_eq!(bvh.node_count(), 1);
+		assert!((bvh.hit_info(&ray).unwrap().distance - 4.0).abs() <= 1e-5);
+	}
+}
@@ -0,0 +1,287 @@
+use clip_space::{ClipSpace, DepthRange, Handedness};
+use matrix::Mat4;
+use quaternion::Quat;
+use ray_tracing::{Ray, Rng};
+use vector::Vec3;
+use vector2::Vec2;
+use Scalar;
+
+/// The conventions of [`Matrix::projection`](../struct.Mat4.html#method.projection), which match [`Matrix::view`](../struct.Mat4.html#method.view)
+const CLIP_SPACE: ClipSpace = ClipSpace {
+	depth: DepthRange::NegativeOneToOne,
+	handedness: Handedness::Left,
+	reverse_z: false,
+};
+
+/// How a [Camera](struct.Camera.html) projects the Scene onto the image
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CameraProjection<T: Scalar = f32> {
+	/// All Rays start at the position of the Camera and spread out with the vertical Field of View `fov_y` in Radians
+	Perspective {
+		/// The vertical Field of View in Radians
+		fov_y: T,
+	},
+	/// All Rays are parallel and start on a plane through the position of the Camera
+	Orthographic {
+		/// The height of the visible area
+		height: T,
+	},
+}
+
+/// A Camera that creates the primary Rays for rendering an image
+///
+/// The Camera uses the same conventions as [`Matrix::look_at`](../struct.Mat4.html#method.look_at)
+/// and [`Matrix::projection`](../struct.Mat4.html#method.projection): in view space, it looks
+/// along +z, with +y up and +x to the right.
+///
+/// With an `aperture` larger than 0, the Camera simulates a thin lens: Rays start on a disk
+/// around the position and converge on the focal plane at `focus_distance`, so that only
+/// Objects near that plane are sharp when averaging many samples.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Camera<T: Scalar = f32> {
+	/// The position of the Camera
+	pub position: Vec3<T>,
+	/// The Rotation from view space into world space
+	pub orientation: Quat<T>,
+	/// How the Scene is projected onto the image
+	pub projection: CameraProjection<T>,
+	/// The aspect ratio (width / height) of the image
+	pub aspect: T,
+	/// The diameter of the lens, or 0 for a pinhole Camera where everything is sharp
+	pub aperture: T,
+	/// The distance along the looking direction at which everything is sharp
+	pub focus_distance: T,
+}
+
+impl<T: Scalar> Camera<T> {
+	/// creates a new pinhole Camera at `position` with the given orientation
+	pub fn new(position: Vec3<T>, orientation: Quat<T>, projection: CameraProjection<T>, aspect: T) -> Camera<T> {
+		Camera {
+			position,
+			orientation,
+			projection,
+			aspect,
+			aperture: T::ZERO,
+			focus_distance: T::ONE,
+		}
+	}
+	/// creates a new perspective Camera at `position` facing `looking_at` with up Vector `up`
+	pub fn look_at(position: Vec3<T>, looking_at: Vec3<T>, up: Vec3<T>, fov_y: T, aspect: T) -> Camera<T> {
+		// the Rotation part of the view Matrix is orthonormal, so its transpose is its inverse
+		let view = Mat4::look_at(position, looking_at, up);
+		let orientation = Quat::from_matrix(&view.transposed());
+		Camera::new(position, orientation, CameraProjection::Perspective { fov_y }, aspect)
+	}
+	/// Returns the same Camera with an orthographic projection that shows an area of the given `height`
+	pub fn orthographic(self, height: T) -> Camera<T> {
+		Camera {
+			projection: CameraProjection::Orthographic { height },
+			..self
+		}
+	}
+	/// Returns the same Camera with a thin lens of the given `aperture` diameter that is focused at `focus_distance`
+	pub fn with_lens(self, aperture: T, focus_distance: T) -> Camera<T> {
+		Camera {
+			aperture,
+			focus_distance,
+			..self
+		}
+	}
+	/// Returns the direction that the Camera is looking in
+	pub fn forward(&self) -> Vec3<T> {
+		self.orientation.rotate(Vec3::from((T::ZERO, T::ZERO, T::ONE)))
+	}
+	/// Returns the direction that is to the right on the image
+	pub fn right(&self) -> Vec3<T> {
+		self.orientation.rotate(Vec3::from((T::ONE, T::ZERO, T::ZERO)))
+	}
+	/// Returns the direction that is up on the image
+	pub fn up(&self) -> Vec3<T> {
+		self.orientation.rotate(Vec3::from((T::ZERO, T::ONE, T::ZERO)))
+	}
+	/// creates the View Matrix of the Camera, as created by [`Matrix::view`](../struct.Mat4.html#method.view)
+	pub fn view_matrix(&self) -> Mat4<T> {
+		Mat4::view(self.position, self.forward(), self.up())
+	}
+	/// creates the Projection Matrix of the Camera with the `near` and `far` Boundaries
+	///
+	/// The Matrix follows the conventions of [`Matrix::projection`](../struct.Mat4.html#method.projection)
+	pub fn projection_matrix(&self, near: T, far: T) -> Mat4<T> {
+		match self.projection {
+			CameraProjection::Perspective { fov_y } => Mat4::perspective(fov_y, self.aspect, near, far, CLIP_SPACE),
+			CameraProjection::Orthographic { height } => {
+				let (half_w, half_h) = (height * self.aspect / T::TWO, height / T::TWO);
+				Mat4::orthographic_with(-half_w, half_w, -half_h, half_h, near, far, CLIP_SPACE)
+			}
+		}
+	}
+	/// creates the Ray through a Point in normalized device coordinates, starting at the center of the lens
+	///
+	/// `ndc` ranges from `(-1, -1)` in the bottom left to `(1, 1)` in the top right corner of the image
+	pub fn ray_for_ndc(&self, ndc: Vec2<T>) -> Ray<T> {
+		self.ray_through_lens(ndc, Vec2::new())
+	}
+	/// creates the Ray through a Point in normalized device coordinates, starting at a Point on the lens
+	///
+	/// `lens` is a Point in the unit disk, which is scaled to the aperture. All Rays for the same
+	/// `ndc` meet on the focal plane.
+	pub fn ray_through_lens(&self, ndc: Vec2<T>, lens: Vec2<T>) -> Ray<T> {
+		let (start, direction) = match self.projection {
+			CameraProjection::Perspective { fov_y } => {
+				let half_h = (fov_y / T::TWO).tan();
+				let x = self.right() * (ndc.x * half_h * self.aspect);
+				let y = self.up() * (ndc.y * half_h);
+				(self.position, self.forward() + x + y)
+			}
+			CameraProjection::Orthographic { height } => {
+				let half_h = height / T::TWO;
+				let x = self.right() * (ndc.x * half_h * self.aspect);
+				let y = self.up() * (ndc.y * half_h);
+				(self.position + x + y, self.forward())
+			}
+		};
+		if self.aperture <= T::ZERO {
+			return Ray::new(start, direction);
+		}
+		// the forward component of `direction` is 1, so this is on the focal plane
+		let focus = start + direction * self.focus_distance;
+		let radius = self.aperture / T::TWO;
+		let start = start + self.right() * (lens.x * radius) + self.up() * (lens.y * radius);
+		Ray::new(start, focus - start)
+	}
+	/// creates the Ray through the center of the pixel `(x, y)` of an image with the given dimensions
+	///
+	/// The pixel `(0, 0)` is in the top left corner of the image
+	pub fn ray_for_pixel(&self, x: usize, y: usize, width: usize, height: usize) -> Ray<T> {
+		let half = T::ONE / T::TWO;
+		self.ray_for_subpixel(x, y, width, height, Vec2 { x: half, y: half })
+	}
+	/// creates a Ray through the pixel `(x, y)`, at the `offset` within the pixel
+	///
+	/// `offset` ranges from `(0, 0)` in the top left to `(1, 1)` in the bottom right corner of
+	/// the pixel. Random offsets can be used to anti-alias the image by averaging multiple samples.
+	pub fn ray_for_subpixel(&self, x: usize, y: usize, width: usize, height: usize, offset: Vec2<T>) -> Ray<T> {
+		self.ray_for_ndc(pixel_to_ndc(x, y, width, height, offset))
+	}
+	/// creates a random Ray through the pixel `(x, y)` for anti-aliasing and depth of field
+	///
+	/// Both the Point in the pixel and the Point on the lens are sampled uniformly from `rng`
+	pub fn sample_ray(&self, x: usize, y: usize, width: usize, height: usize, rng: &mut Rng) -> Ray<T> {
+		let ndc = pixel_to_ndc(x, y, width, height, rng.next_square());
+		self.ray_through_lens(ndc, rng.next_disk())
+	}
+}
+
+/// converts a Point within the pixel `(x, y)` to normalized device coordinates
+fn pixel_to_ndc<T: Scalar>(x: usize, y: usize, width: usize, height: usize, offset: Vec2<T>) -> Vec2<T> {
+	let u = (T::from_f64(x as f64) + offset.x) / T::from_f64(width as f64);
+	let v = (T::from_f64(y as f64) + offset.y) / T::from_f64(height as f64);
+	Vec2 {
+		x: u * T::TWO - T::ONE,
+		y: T::ONE - v * T::TWO,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use matrix::Matrix;
+	use vector::Vector;
+
+	fn assert_vec_eq(a: Vector, b: Vector) {
+		assert!((a - b).length() <= 1e-5, "{} != {}", a, b);
+	}
+
+	#[test]
+	fn camera_matches_matrices() {
+		let position = Vector::from((1.0, 2.0, 3.0));
+		let camera = Camera::look_at(position, Vector::from((4.0, 0.0, -2.0)), Vector::from((0.0, 1.0, 0.0)), 1.1, 1.6);
+		assert_vec_eq(camera.forward(), (Vector::from((4.0, 0.0, -2.0)) - position).norm());
+		let view = camera.view_matrix();
+		let expected = Matrix::look_at(position, Vector::from((4.0, 0.0, -2.0)), Vector::from((0.0, 1.0, 0.0)));
+		for i in 0..4 {
+			for j in 0..4 {
+				assert!((view[i][j] - expected[i][j]).abs() <= 1e-5);
+			}
+		}
+		let projection = camera.projection_matrix(0.1, 100.0);
+		let legacy = Matrix::projection((16, 10), 1.1, 0.1, 100.0);
+		for i in 0..4 {
+			for j in 0..4 {
+				assert!((projection[i][j] - legacy[i][j]).abs() <= 1e-4);
+			}
+		}
+
+		for &camera in &[camera, camera.orthographic(3.0)] {
+			let clip = camera.projection_matrix(0.1, 100.0) * camera.view_matrix();
+			for &point in &[Vector::from((2.0, 1.0, -1.0)), Vector::from((3.0, 2.5, -4.0))] {
+				let ndc = clip * point;
+				let ray = camera.ray_for_ndc(Vec2 { x: ndc.x, y: ndc.y });
+				let t = (point - ray.start) * ray.direction;
+				assert_vec_eq(ray.at(t), point);
+			}
+		}
+	}
+
+	#[test]
+	fn camera_pixels() {
+		let camera = Camera::new(Vector::new(), Quat::identity(), CameraProjection::Perspective { fov_y: 1.0 }, 2.0);
+		assert_vec_eq(camera.ray_for_ndc(Vec2::new()).direction, Vector::from((0.0, 0.0, 1.0)));
+		// the center of a 2x2 image is the corner between all pixels
+		let corner = camera.ray_for_subpixel(0, 0, 2, 2, Vec2 { x: 1.0, y: 1.0 });
+		assert_vec_eq(corner.direction, Vector::from((0.0, 0.0, 1.0)));
+
+		let top_left = camera.ray_for_subpixel(0, 0, 4, 2, Vec2::new()).direction;
+		let half_h = 0.5f32.tan();
+		assert_vec_eq(top_left, Vector::from((-2.0 * half_h, half_h, 1.0)).norm());
+		let pixel = camera.ray_for_pixel(3, 1, 4, 2).direction;
+		assert_vec_eq(pixel, Vector::from((1.5 * half_h, -0.5 * half_h, 1.0)).norm());
+
+		let ortho = camera.orthographic(2.0);
+		let ray = ortho.ray_for_subpixel(0, 0, 4, 2, Vec2::new());
+		assert_vec_eq(ray.start, Vector::from((-2.0, 1.0, 0.0)));
+		assert_vec_eq(ray.direction, Vector::from((0.0, 0.0, 1.0)));
+	}
+	#[test]
+	fn camera_depth_of_field() {
+		let camera = Camera::look_at(Vector::new(), Vector::from((0.0, 0.0, -1.0)), Vector::from((0.0, 1.0, 0.0)), 1.0, 1.5).with_lens(0.5, 4.0);
+		let ndc = Vec2 { x: 0.3, y: -0.6 };
+		let center = camera.ray_for_ndc(ndc);
+		assert_vec_eq(center.start, Vector::new());
+		let focus = center.at(4.0 / (center.direction * camera.forward()));
+
+		// Rays from anywhere on the lens meet on the focal plane
+		let mut rng = Rng::new(7);
+		for _ in 0..20 {
+			let lens = rng.next_disk();
+			let ray = camera.ray_through_lens(ndc, lens);
+			assert!((ray.start - center.start).length() <= 0.25 + 1e-6);
+			let t = (focus - ray.start) * ray.direction;
+			assert_vec_eq(ray.at(t), focus);
+		}
+		let ortho = camera.orthographic(2.0);
+		let ray = ortho.ray_through_lens(ndc, Vec2 { x: 1.0, y: 0.0 });
+		assert_vec_eq(ray.at((ortho.ray_for_ndc(ndc).at(4.0) - ray.start).length()), ortho.ray_for_ndc(ndc).at(4.0));
+
+		// sampling is reproducible and stays within the pixel and the lens
+		let sample = |seed| {
+			let mut rng = Rng::new(seed);
+			(0..8).map(|_| camera.sample_ray(2, 1, 4, 3, &mut rng)).collect::<Vec<_>>()
+		};
+		let (a, b) = (sample(3), sample(3));
+		for (a, b) in a.iter().zip(&b) {
+			assert_eq!(a.start, b.start);
+			assert_eq!(a.direction, b.direction);
+		}
+		assert!(a.iter().zip(&sample(4)).any(|(a, b)| a.direction != b.direction));
+		let pinhole = camera.with_lens(0.0, 4.0);
+		let (corner, opposite) = (pinhole.ray_for_subpixel(2, 1, 4, 3, Vec2::new()), pinhole.ray_for_subpixel(2, 1, 4, 3, Vec2 { x: 1.0, y: 1.0 }));
+		for ray in &a {
+			assert!(ray.start.length() <= 0.25 + 1e-6);
+			let p = ray.at(4.0 / (ray.direction * camera.forward()));
+			let (a, b) = (corner.at(4.0 / corner.direction.z.abs()), opposite.at(4.0 / opposite.direction.z.abs()));
+			let (min, max) = (a.min(b) - Vector::from((1e-5, 1e-5, 1e-5)), a.max(b) + Vector::from((1e-5, 1e-5, 1e-5)));
+			assert!(min.x <= p.x && p.x <= max.x && min.y <= p.y && p.y <= max.y, "{} not in {} - {}", p, min, max);
+		}
+	}
+}
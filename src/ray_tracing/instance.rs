@@ -0,0 +1,144 @@
+use matrix::Mat4;
+use matrix3::Mat3;
+use ray_tracing::{HitInfo, Ray, RayTarget};
+use shapes::{Aabb, Bounded};
+use Scalar;
+
+/// A RayTarget placed in the world with a transformation
+///
+/// Rays are transformed into the space of the Object instead of transforming the Object itself,
+/// so the same Object can be placed many times without copying it. Wrap the Object in an
+/// [`Rc`](https://doc.rust-lang.org/std/rc/struct.Rc.html) or [`Arc`](https://doc.rust-lang.org/std/sync/struct.Arc.html)
+/// to share it between Instances.
+///
+/// The parametric distance of a hit is the same in both spaces, because the direction of the
+/// transformed Ray is not normalized.
+#[derive(Clone, Debug)]
+pub struct Instance<R, T: Scalar = f32> {
+	object: R,
+	transform: Mat4<T>,
+	inverse: Mat4<T>,
+	normal_matrix: Mat3<T>,
+}
+
+impl<R, T: Scalar> Instance<R, T> {
+	/// places `object` in the world with `transform`, which maps from object space to world space
+	///
+	/// returns None if `transform` can't be inverted
+	pub fn new(object: R, transform: Mat4<T>) -> Option<Instance<R, T>> {
+		let inverse = transform.try_inverse()?;
+		Some(Instance {
+			object,
+			transform,
+			inverse,
+			normal_matrix: transform.normal_matrix(),
+		})
+	}
+	/// Returns the placed Object
+	pub fn object(&self) -> &R {
+		&self.object
+	}
+	/// Returns the placed Object, discarding the transformation
+	pub fn into_object(self) -> R {
+		self.object
+	}
+	/// Returns the Matrix that transforms from object space to world space
+	pub fn transform(&self) -> &Mat4<T> {
+		&self.transform
+	}
+	/// Returns the Matrix that transforms from world space to object space
+	pub fn inverse(&self) -> &Mat4<T> {
+		&self.inverse
+	}
+	/// transforms a Ray from world space to object space, keeping its interval
+	pub fn to_object_space(&self, ray: &Ray<T>) -> Ray<T> {
+		Ray {
+			start: self.inverse.transform_point(ray.start),
+			direction: self.inverse.transform_vector(ray.direction),
+			..*ray
+		}
+	}
+}
+
+impl<R: Bounded<T>, T: Scalar> Bounded<T> for Instance<R, T> {
+	fn bounds(&self) -> Aabb<T> {
+		self.object.bounds().transform(&self.transform)
+	}
+}
+
+impl<R: RayTarget<T>, T: Scalar> RayTarget<T> for Instance<R, T> {
+	fn hit_info(&self, ray: &Ray<T>) -> Option<HitInfo<T>> {
+		let hit = self.object.hit_info(&self.to_object_space(ray))?;
+		Some(HitInfo {
+			point: self.transform.transform_point(hit.point),
+			normal: (self.normal_matrix * hit.normal).norm(),
+			..hit
+		})
+	}
+	fn hits(&self, ray: &Ray<T>) -> bool {
+		self.object.hits(&self.to_object_space(ray))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use matrix::Matrix;
+	use quaternion::Quat;
+	use ray_tracing::Bvh;
+	use shapes::{Sphere, Triangle};
+	use std::rc::Rc;
+	use vector::{Vec3, Vector};
+
+	fn assert_vec_eq(a: Vector, b: Vector) {
+		assert!((a - b).length() <= 1e-5, "{} != {}", a, b);
+	}
+
+	#[test]
+	fn instance_transforms_hits() {
+		// a unit Sphere stretched into an ellipsoid with half-axes (2, 1, 1) at x = 10
+		let transform = Mat4::from_trs(Vector::from((10.0, 0.0, 0.0)), Quat::identity(), Vector::from((2.0, 1.0, 1.0)));
+		let instance = Instance::new(Sphere::new(Vector::new(), 1.0), transform).unwrap();
+
+		let ray = Ray::new(Vector::new(), Vector::from((1.0, 0.0, 0.0)));
+		let hit = instance.hit_info(&ray).unwrap();
+		assert!((hit.distance - 8.0).abs() <= 1e-5);
+		assert_vec_eq(hit.point, Vector::from((8.0, 0.0, 0.0)));
+		assert_vec_eq(hit.normal, Vector::from((-1.0, 0.0, 0.0)));
+		assert!(instance.hit_info(&ray.with_interval(0.0, 7.0)).is_none());
+
+		// the normal of a stretched Sphere is not the direction from the center
+		let point = Vector::from((10.0 + 2.0 * 0.6, 0.8, 0.0));
+		let hit = instance.hit_info(&Ray::new(Vector::from((point.x, 5.0, 0.0)), Vector::from((0.0, -1.0, 0.0)))).unwrap();
+		assert_vec_eq(hit.point, point);
+		assert_vec_eq(hit.normal, Vector::from((0.3, 0.8, 0.0)).norm());
+		assert!(!instance.hits(&Ray::new(Vector::from((0.0, 2.0, 0.0)), Vector::from((1.0, 0.0, 0.0)))));
+
+		let bounds = instance.bounds();
+		assert_vec_eq(bounds.min, Vector::from((8.0, -1.0, -1.0)));
+		assert_vec_eq(bounds.max, Vector::from((12.0, 1.0, 1.0)));
+		assert!(Instance::new(Sphere::new(Vector::new(), 1.0), Matrix::new()).is_none());
+	}
+
+	#[test]
+	fn instance_shared_object() {
+		let triangle: Rc<Triangle> = Rc::new(Triangle::new(Vector::new(), Vector::from((1.0, 0.0, 0.0)), Vector::from((0.0, 1.0, 0.0))));
+		let instances: Vec<_> = (0..4)
+			.map(|i| {
+				let rotation = Quat::from_axis_angle(Vector::from((0.0, 1.0, 0.0)), ::std::f32::consts::PI);
+				let transform = Mat4::from_trs(Vector::from((3.0 * i as f32, 0.0, 0.0)), rotation, Vec3::from((1.0, 1.0, 1.0)));
+				Instance::new(triangle.clone(), transform).unwrap()
+			})
+			.collect();
+		assert_eq!(Rc::strong_count(&triangle), 5);
+
+		// the Triangle is rotated around y, so it spans x in [3i - 1, 3i] and faces -z
+		let bvh = Bvh::new(instances);
+		let (index, hit) = bvh.nearest_hit(&Ray::new(Vector::from((5.75, 0.1, 5.0)), Vector::from((0.0, 0.0, -1.0)))).unwrap();
+		assert_eq!(index, 2);
+		assert_vec_eq(hit.point, Vector::from((5.75, 0.1, 0.0)));
+		assert_vec_eq(hit.normal, Vector::from((0.0, 0.0, -1.0)));
+		assert!(bvh.any_hit(&Ray::new(Vector::from((8.5, 0.2, -5.0)), Vector::from((0.0, 0.0, 1.0)))));
+		assert!(!bvh.any_hit(&Ray::new(Vector::from((1.5, 0.2, -5.0)), Vector::from((0.0, 0.0, 1.0)))));
+	}
+}
@@ -5,3 +5,18 @@ pub use self::ray_target::*;
 
 mod ray;
 pub use self::ray::*;
+
+mod bvh;
+pub use self::bvh::*;
+
+mod camera;
+pub use self::camera::*;
+
+mod sampler;
+pub use self::sampler::*;
+
+mod scene;
+pub use self::scene::*;
+
+mod instance;
+pub use self::instance::*;
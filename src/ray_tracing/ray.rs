@@ -1,30 +1,99 @@
 use ray_tracing::HitInfo;
-use vector::Vector;
+use vector::Vec3;
+use Scalar;
 
 /// A Ray in 3D-Space
 #[derive(Clone, Copy, Debug)]
-pub struct Ray {
+pub struct Ray<T: Scalar = f32> {
 	/// The starting Point of the Ray
-	pub start: Vector,
+	pub start: Vec3<T>,
 	/// The direction where the Ray is headed
 	///
 	/// should be normalized, but is not guaranteed to be
-	pub direction: Vector,
+	pub direction: Vec3<T>,
+	/// The smallest parametric distance along the Ray where hits are accepted
+	pub t_min: T,
+	/// The largest parametric distance along the Ray where hits are accepted
+	///
+	/// This can be lowered to the distance of the closest hit so far, so that farther Objects can be skipped
+	pub t_max: T,
 }
 
-impl Ray {
+impl<T: Scalar> Ray<T> {
 	/// creates a new Ray with the given starting Point and direction
 	///
-	/// automatically normalizes the direction
-	pub fn new(start: Vector, direction: Vector) -> Ray {
+	/// automatically normalizes the direction. The Ray accepts hits in `[0, infinity]`
+	pub fn new(start: Vec3<T>, direction: Vec3<T>) -> Ray<T> {
 		Ray {
 			start,
 			direction: direction.norm(),
+			t_min: T::ZERO,
+			t_max: T::INFINITY,
+		}
+	}
+	/// creates a new Ray from `start` to `end` that only accepts hits between these Points
+	///
+	/// Useful for checking if anything is in the way between two Points, like for shadow Rays.
+	/// If `start` and `end` are the same Point, the direction is the z axis and only hits at
+	/// distance 0 are accepted.
+	pub fn segment(start: Vec3<T>, end: Vec3<T>) -> Ray<T> {
+		let delta = end - start;
+		let length = delta.length();
+		if length == T::ZERO {
+			let direction = Vec3 {
+				x: T::ZERO,
+				y: T::ZERO,
+				z: T::ONE,
+			};
+			return Ray::new(start, direction).with_interval(T::ZERO, T::ZERO);
 		}
+		Ray::new(start, delta).with_interval(T::ZERO, length)
+	}
+	/// Returns the same Ray, but only accepting hits with a distance in `[t_min, t_max]`
+	pub fn with_interval(self, t_min: T, t_max: T) -> Ray<T> {
+		Ray {
+			t_min,
+			t_max,
+			..self
+		}
+	}
+	/// Checks if the parametric distance `t` is within `[t_min, t_max]`
+	pub fn accepts(&self, t: T) -> bool {
+		self.t_min <= t && t <= self.t_max
+	}
+	/// Returns the Point at the parametric distance `t` along the Ray
+	pub fn at(&self, t: T) -> Vec3<T> {
+		self.start + self.direction * t
 	}
 	/// Returns a Ray that is the result of reflecting this Ray at the hit Point
-	pub fn reflect(&self, hit: &HitInfo) -> Ray {
-		let dir = self.direction - hit.normal * 2.0 * (hit.normal * self.direction);
-		Ray::new(hit.point, dir)
+	///
+	/// The reflected Ray ignores hits very close to its start, so that rounding errors don't
+	/// cause it to hit the Object it was reflected from.
+	pub fn reflect(&self, hit: &HitInfo<T>) -> Ray<T> {
+		let dir = self.direction - hit.normal * T::TWO * (hit.normal * self.direction);
+		let offset = (hit.point.length() + T::ONE) * T::EPSILON.sqrt();
+		Ray::new(hit.point, dir).with_interval(offset, T::INFINITY)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use ray_tracing::RayTarget;
+	use shapes::Sphere;
+	use vector::Vector;
+
+	#[test]
+	fn ray_segment() {
+		let ray = Ray::segment(Vector::new(), Vector::from((0.0, 3.0, 4.0)));
+		assert_eq!(ray.direction, Vector::from((0.0, 0.6, 0.8)));
+		assert!((ray.t_max - 5.0).abs() <= 1e-6);
+
+		// an empty segment keeps a finite direction instead of dividing by zero
+		let point = Vector::from((1.0, 2.0, 3.0));
+		let ray = Ray::segment(point, point);
+		assert!(ray.direction.length().is_finite());
+		assert_eq!(ray.t_max, 0.0);
+		assert!(!Sphere::new(Vector::from((1.0, 2.0, 10.0)), 1.0).hits(&ray));
 	}
 }
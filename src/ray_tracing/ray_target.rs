@@ -1,39 +1,94 @@
+use std::rc::Rc;
+use std::sync::Arc;
+
 use ray_tracing::Ray;
-use vector::Vector;
+use vector::Vec3;
+use vector2::Vec2;
+use Scalar;
 
 /// A struct to store info about a Raycasting hit
 ///
-/// The only necessary information that should be provided by any RayTarget is the point
-/// and normal of the hit. Any additional information may be useful to the caster of the Ray,
+/// The only necessary information that should be provided by any RayTarget is the point,
+/// distance and normal of the hit. Any additional information may be useful to the caster of the Ray,
 /// but is not necessarily provided by all Implementations of RayTarget.
 #[derive(Default)]
-pub struct HitInfo {
+pub struct HitInfo<T: Scalar = f32> {
 	/// The Point where the Ray hit
-	pub point: Vector,
+	pub point: Vec3<T>,
+	/// The parametric distance along the Ray, so that `point = ray.start + ray.direction * distance`
+	///
+	/// This is the actual distance if the direction of the Ray is normalized
+	pub distance: T,
 	/// The Normal of the Object at the hit
 	///
 	/// This may be used to calculate a reflected Ray
-	pub normal: Vector,
+	pub normal: Vec3<T>,
 	/// The Color of the Object at the hit (_optional_)
 	pub color: Option<u32>,
 	/// The "reflectiveness" of the Object (_optional_)
-	pub reflect_factor: Option<f32>,
+	pub reflect_factor: Option<T>,
+	/// The barycentric coordinates of the hit on a Triangle (_optional_)
+	///
+	/// The Components are the weights of the three corners, so that
+	/// `point = corners[0] * x + corners[1] * y + corners[2] * z`
+	pub barycentric: Option<Vec3<T>>,
+	/// The texture coordinates of the Object at the hit (_optional_)
+	pub uv: Option<Vec2<T>>,
 }
 
 /// A Trait for handling Raycasting on an Object
-pub trait RayTarget {
+pub trait RayTarget<T: Scalar = f32> {
 	/// get the full info of a Ray hit
-	/// 
+	///
+	/// Only hits with a distance in `[ray.t_min, ray.t_max]` are considered. If the Ray hits the
+	/// Object multiple times in that interval, the closest hit is returned.
+	///
 	/// returns None if the Ray does not hit the Object
-	fn hit_info(&self, ray: &Ray) -> Option<HitInfo>;
+	fn hit_info(&self, ray: &Ray<T>) -> Option<HitInfo<T>>;
 	/// get the Point where a Ray hits
 	/// 
 	/// returns None if the Ray does not hit the Object
-	fn hit_point(&self, ray: &Ray) -> Option<Vector> {
+	fn hit_point(&self, ray: &Ray<T>) -> Option<Vec3<T>> {
 		self.hit_info(ray).map(|info| info.point)
 	}
 	/// test if a Ray hits
-	fn hits(&self, ray: &Ray) -> bool {
+	fn hits(&self, ray: &Ray<T>) -> bool {
 		self.hit_point(ray).is_some()
 	}
 }
+
+impl<T: Scalar, R: RayTarget<T> + ?Sized> RayTarget<T> for &R {
+	fn hit_info(&self, ray: &Ray<T>) -> Option<HitInfo<T>> {
+		(**self).hit_info(ray)
+	}
+	fn hits(&self, ray: &Ray<T>) -> bool {
+		(**self).hits(ray)
+	}
+}
+
+impl<T: Scalar, R: RayTarget<T> + ?Sized> RayTarget<T> for Box<R> {
+	fn hit_info(&self, ray: &Ray<T>) -> Option<HitInfo<T>> {
+		(**self).hit_info(ray)
+	}
+	fn hits(&self, ray: &Ray<T>) -> bool {
+		(**self).hits(ray)
+	}
+}
+
+impl<T: Scalar, R: RayTarget<T> + ?Sized> RayTarget<T> for Rc<R> {
+	fn hit_info(&self, ray: &Ray<T>) -> Option<HitInfo<T>> {
+		(**self).hit_info(ray)
+	}
+	fn hits(&self, ray: &Ray<T>) -> bool {
+		(**self).hits(ray)
+	}
+}
+
+impl<T: Scalar, R: RayTarget<T> + ?Sized> RayTarget<T> for Arc<R> {
+	fn hit_info(&self, ray: &Ray<T>) -> Option<HitInfo<T>> {
+		(**self).hit_info(ray)
+	}
+	fn hits(&self, ray: &Ray<T>) -> bool {
+		(**self).hits(ray)
+	}
+}
@@ -0,0 +1,89 @@
+use vector2::Vec2;
+use Scalar;
+
+/// A small and fast pseudo random number generator for sampling
+///
+/// The sequence of numbers only depends on the seed, so results are reproducible. This uses
+/// the [SplitMix64](https://prng.di.unimi.it/splitmix64.c) algorithm, which is not
+/// cryptographically secure.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Rng {
+	state: u64,
+}
+
+impl Rng {
+	/// creates a new Rng that produces the sequence determined by `seed`
+	pub fn new(seed: u64) -> Rng {
+		Rng { state: seed }
+	}
+	/// Returns the next random `u64`
+	pub fn next_u64(&mut self) -> u64 {
+		self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+		let mut z = self.state;
+		z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+		z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+		z ^ (z >> 31)
+	}
+	/// Returns a uniformly distributed number in `[0, 1)`
+	pub fn next_scalar<T: Scalar>(&mut self) -> T {
+		// the upper 53 bits fit exactly into the mantissa of an f64
+		let value = (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64;
+		// rounding to f32 could produce exactly 1
+		T::from_f64(value).min(T::ONE - T::EPSILON)
+	}
+	/// Returns a uniformly distributed Point in `[0, 1)²`
+	pub fn next_square<T: Scalar>(&mut self) -> Vec2<T> {
+		Vec2 {
+			x: self.next_scalar(),
+			y: self.next_scalar(),
+		}
+	}
+	/// Returns a uniformly distributed Point inside of the unit disk
+	///
+	/// Uses the concentric mapping by Shirley and Chiu, which needs exactly two random numbers per Point
+	pub fn next_disk<T: Scalar>(&mut self) -> Vec2<T> {
+		let square: Vec2<T> = self.next_square();
+		let a = square.x * T::TWO - T::ONE;
+		let b = square.y * T::TWO - T::ONE;
+		if a == T::ZERO && b == T::ZERO {
+			return Vec2::new();
+		}
+		let quarter_pi = T::PI / T::from_f64(4.0);
+		let (radius, angle) = if a.abs() > b.abs() {
+			(a, quarter_pi * (b / a))
+		} else {
+			(b, T::PI / T::TWO - quarter_pi * (a / b))
+		};
+		let (sin, cos) = angle.sin_cos();
+		Vec2 {
+			x: radius * cos,
+			y: radius * sin,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn rng_sequence() {
+		let mut a = Rng::new(42);
+		let mut b = Rng::new(42);
+		let first: Vec<u64> = (0..10).map(|_| a.next_u64()).collect();
+		assert_eq!(first, (0..10).map(|_| b.next_u64()).collect::<Vec<_>>());
+		assert_ne!(first, (0..10).map(|_| Rng::new(43).next_u64()).collect::<Vec<_>>());
+		// reference value of SplitMix64 with seed 0
+		assert_eq!(Rng::new(0).next_u64(), 0xE220_A839_7B1D_CDAF);
+
+		let mut sum = 0.0;
+		for _ in 0..10_000 {
+			let x: f64 = a.next_scalar();
+			assert!((0.0..1.0).contains(&x));
+			sum += x;
+			let p: Vec2<f32> = a.next_disk();
+			assert!(p.length() <= 1.0 + 1e-6);
+		}
+		assert!((sum / 10_000.0 - 0.5).abs() < 0.02);
+	}
+}
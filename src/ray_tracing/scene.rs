@@ -0,0 +1,214 @@
+use std::fmt;
+
+use ray_tracing::{HitInfo, Ray, RayTarget};
+use Scalar;
+
+/// A handle to an Object in a [Scene](struct.Scene.html)
+///
+/// Handles stay valid until their Object is removed. A handle of a removed Object never refers
+/// to a different Object, even if its slot is reused.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ObjectId {
+	index: u32,
+	generation: u32,
+}
+
+/// A storage slot for an Object, which may be reused after the Object was removed
+struct Slot<T: Scalar> {
+	generation: u32,
+	object: Option<Box<dyn RayTarget<T>>>,
+}
+
+/// A collection of different RayTargets that can be raycast together
+///
+/// The Scene tests every Object for each Ray. For many small primitives like Triangles, it is
+/// faster to add them as a single [Bvh](struct.Bvh.html) or [Mesh](../shapes/struct.Mesh.html).
+pub struct Scene<T: Scalar = f32> {
+	slots: Vec<Slot<T>>,
+	free: Vec<u32>,
+	len: usize,
+}
+
+impl<T: Scalar> Scene<T> {
+	/// creates a new empty Scene
+	pub fn new() -> Scene<T> {
+		Scene {
+			slots: Vec::new(),
+			free: Vec::new(),
+			len: 0,
+		}
+	}
+	/// Returns the number of Objects in the Scene
+	pub fn len(&self) -> usize {
+		self.len
+	}
+	/// checks if there are no Objects in the Scene
+	pub fn is_empty(&self) -> bool {
+		self.len == 0
+	}
+	/// adds an Object to the Scene
+	///
+	/// returns the handle that identifies the Object in hits and for removing it
+	pub fn add<R: RayTarget<T> + 'static>(&mut self, object: R) -> ObjectId {
+		self.add_boxed(Box::new(object))
+	}
+	/// adds an already boxed Object to the Scene
+	///
+	/// returns the handle that identifies the Object in hits and for removing it
+	pub fn add_boxed(&mut self, object: Box<dyn RayTarget<T>>) -> ObjectId {
+		self.len += 1;
+		if let Some(index) = self.free.pop() {
+			let slot = &mut self.slots[index as usize];
+			slot.object = Some(object);
+			return ObjectId {
+				index,
+				generation: slot.generation,
+			};
+		}
+		let index = self.slots.len() as u32;
+		self.slots.push(Slot {
+			generation: 0,
+			object: Some(object),
+		});
+		ObjectId { index, generation: 0 }
+	}
+	/// removes an Object from the Scene
+	///
+	/// returns the removed Object, or None if the handle doesn't refer to an Object of this Scene
+	pub fn remove(&mut self, id: ObjectId) -> Option<Box<dyn RayTarget<T>>> {
+		let slot = self.slots.get_mut(id.index as usize).filter(|s| s.generation == id.generation)?;
+		let object = slot.object.take()?;
+		// a slot whose generation would overflow is retired instead of being reused
+		if let Some(generation) = slot.generation.checked_add(1) {
+			slot.generation = generation;
+			self.free.push(id.index);
+		}
+		self.len -= 1;
+		Some(object)
+	}
+	/// removes all Objects, invalidating all handles
+	pub fn clear(&mut self) {
+		let ids: Vec<ObjectId> = self.iter().map(|(id, _)| id).collect();
+		for id in ids {
+			self.remove(id);
+		}
+	}
+	/// checks if the handle refers to an Object of this Scene
+	pub fn contains(&self, id: ObjectId) -> bool {
+		self.get(id).is_some()
+	}
+	/// Returns the Object with the given handle
+	pub fn get(&self, id: ObjectId) -> Option<&dyn RayTarget<T>> {
+		let slot = self.slots.get(id.index as usize).filter(|s| s.generation == id.generation)?;
+		slot.object.as_deref()
+	}
+	/// iterates over all Objects together with their handles
+	pub fn iter(&self) -> impl Iterator<Item = (ObjectId, &dyn RayTarget<T>)> {
+		self.slots.iter().enumerate().filter_map(|(index, slot)| {
+			let id = ObjectId {
+				index: index as u32,
+				generation: slot.generation,
+			};
+			slot.object.as_deref().map(|o| (id, o))
+		})
+	}
+	/// finds the closest hit of the Ray with any Object
+	///
+	/// returns the handle of the hit Object together with the hit, or None if nothing is hit
+	pub fn nearest_hit(&self, ray: &Ray<T>) -> Option<(ObjectId, HitInfo<T>)> {
+		let mut ray = *ray;
+		let mut closest = None;
+		for (id, object) in self.iter() {
+			if let Some(hit) = object.hit_info(&ray) {
+				ray.t_max = hit.distance;
+				closest = Some((id, hit));
+			}
+		}
+		closest
+	}
+	/// checks if the Ray hits any Object
+	///
+	/// This stops at the first hit that is found, which makes it faster than
+	/// [nearest_hit](#method.nearest_hit) for occlusion tests like shadow Rays.
+	pub fn any_hit(&self, ray: &Ray<T>) -> bool {
+		self.iter().any(|(_, object)| object.hits(ray))
+	}
+}
+
+impl<T: Scalar> Default for Scene<T> {
+	fn default() -> Scene<T> {
+		Scene::new()
+	}
+}
+
+impl<T: Scalar> fmt::Debug for Scene<T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_struct("Scene").field("len", &self.len).finish()
+	}
+}
+
+impl<T: Scalar> RayTarget<T> for Scene<T> {
+	fn hit_info(&self, ray: &Ray<T>) -> Option<HitInfo<T>> {
+		self.nearest_hit(ray).map(|(_, hit)| hit)
+	}
+	fn hits(&self, ray: &Ray<T>) -> bool {
+		self.any_hit(ray)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use shapes::{Plane, Sphere, Triangle};
+	use vector::Vector;
+
+	#[test]
+	fn scene_nearest_hit() {
+		let mut scene = Scene::new();
+		let floor = scene.add(Plane::new(Vector::from((0.0, -1.0, 0.0)), Vector::from((0.0, 1.0, 0.0))));
+		let far = scene.add(Sphere::new(Vector::from((0.0, 0.0, 10.0)), 1.0));
+		let near = scene.add(Triangle::new(Vector::from((-1.0, -1.0, 5.0)), Vector::from((1.0, -1.0, 5.0)), Vector::from((0.0, 1.0, 5.0))));
+		assert_eq!(scene.len(), 3);
+
+		let ray = Ray::new(Vector::new(), Vector::from((0.0, 0.0, 1.0)));
+		let (id, hit) = scene.nearest_hit(&ray).unwrap();
+		assert_eq!(id, near);
+		assert!((hit.distance - 5.0).abs() <= 1e-6);
+		assert!(scene.hit_info(&ray.with_interval(6.0, 20.0)).is_some_and(|h| (h.distance - 9.0).abs() <= 1e-6));
+		assert_eq!(scene.nearest_hit(&Ray::new(Vector::new(), Vector::from((0.0, -1.0, 1.0)))).unwrap().0, floor);
+
+		// shadow Rays only check the segment between the Points
+		assert!(scene.any_hit(&Ray::segment(Vector::new(), Vector::from((0.0, 0.0, 20.0)))));
+		assert!(!scene.any_hit(&Ray::segment(Vector::new(), Vector::from((0.0, 0.0, 4.0)))));
+		assert!(!scene.hits(&Ray::new(Vector::new(), Vector::from((0.0, 1.0, 0.0)))));
+
+		assert!(scene.remove(near).is_some());
+		assert_eq!(scene.nearest_hit(&ray).unwrap().0, far);
+		assert!(scene.remove(far).is_some());
+		assert!(scene.nearest_hit(&ray).is_none());
+	}
+
+	#[test]
+	fn scene_handles() {
+		let mut scene: Scene = Scene::default();
+		let a = scene.add(Sphere::new(Vector::new(), 1.0));
+		let b = scene.add_boxed(Box::new(Sphere::new(Vector::from((5.0, 0.0, 0.0)), 1.0)));
+		assert_ne!(a, b);
+		assert!(scene.remove(a).is_some());
+		assert!(scene.remove(a).is_none());
+		assert!(!scene.contains(a));
+
+		// the slot is reused, but the old handle stays invalid
+		let c = scene.add(Sphere::new(Vector::from((0.0, 5.0, 0.0)), 1.0));
+		assert_ne!(a, c);
+		assert!(scene.get(a).is_none());
+		assert!(scene.remove(a).is_none());
+		assert!(scene.contains(c));
+		assert_eq!(scene.iter().map(|(id, _)| id).collect::<Vec<_>>(), vec![c, b]);
+
+		scene.clear();
+		assert!(scene.is_empty());
+		assert!(!scene.contains(b) && !scene.contains(c));
+		assert_eq!(format!("{:?}", scene), "Scene { len: 0 }");
+	}
+}
@@ -0,0 +1,141 @@
+use std::fmt::{Debug, Display};
+use std::iter::Sum;
+use std::ops::*;
+
+/// A floating point Type that can be used as the Components of Vectors and Matrices
+///
+/// This Trait is implemented for `f32` and `f64`. It mirrors the parts of the inherent
+/// float API that are needed by this Library, so that all Types can be generic over their precision.
+pub trait Scalar:
+	Copy
+	+ Debug
+	+ Default
+	+ Display
+	+ PartialEq
+	+ PartialOrd
+	+ Add<Output = Self>
+	+ Sub<Output = Self>
+	+ Mul<Output = Self>
+	+ Div<Output = Self>
+	+ Neg<Output = Self>
+	+ AddAssign
+	+ SubAssign
+	+ MulAssign
+	+ DivAssign
+	+ Sum
+{
+	/// The value 0
+	const ZERO: Self;
+	/// The value 1
+	const ONE: Self;
+	/// The value 2
+	const TWO: Self;
+	/// The machine epsilon of the Type, used for approximate comparisons
+	const EPSILON: Self;
+	/// Archimedes' constant (π)
+	const PI: Self;
+	/// Positive infinity
+	const INFINITY: Self;
+
+	/// Converts an `f64` into this Type, possibly losing precision
+	fn from_f64(value: f64) -> Self;
+	/// Converts this value into an `f64`
+	fn to_f64(self) -> f64;
+
+	/// Returns the square root of the value
+	fn sqrt(self) -> Self;
+	/// Returns the absolute value
+	fn abs(self) -> Self;
+	/// Returns the smaller of two values
+	fn min(self, other: Self) -> Self;
+	/// Returns the larger of two values
+	fn max(self, other: Self) -> Self;
+	/// Returns the largest integer less than or equal to the value
+	fn floor(self) -> Self;
+	/// Returns `1` if the value is positive (including `+0.0`), `-1` otherwise
+	fn signum(self) -> Self;
+	/// Returns `true` if the value is neither infinite nor NaN
+	fn is_finite(self) -> bool;
+
+	/// Returns the sine of the value (in Radians)
+	fn sin(self) -> Self;
+	/// Returns the cosine of the value (in Radians)
+	fn cos(self) -> Self;
+	/// Returns the tangent of the value (in Radians)
+	fn tan(self) -> Self;
+	/// Returns the sine and cosine of the value (in Radians)
+	fn sin_cos(self) -> (Self, Self);
+	/// Returns the arcsine of the value in Radians
+	fn asin(self) -> Self;
+	/// Returns the arccosine of the value in Radians
+	fn acos(self) -> Self;
+	/// Returns the four quadrant arctangent of `self` (y) and `other` (x) in Radians
+	fn atan2(self, other: Self) -> Self;
+}
+
+macro_rules! impl_scalar {
+	($t: ident) => {
+		impl Scalar for $t {
+			const ZERO: $t = 0.0;
+			const ONE: $t = 1.0;
+			const TWO: $t = 2.0;
+			const EPSILON: $t = $t::EPSILON;
+			const PI: $t = ::std::$t::consts::PI;
+			const INFINITY: $t = $t::INFINITY;
+
+			fn from_f64(value: f64) -> $t {
+				value as $t
+			}
+			fn to_f64(self) -> f64 {
+				f64::from(self)
+			}
+
+			fn sqrt(self) -> $t {
+				$t::sqrt(self)
+			}
+			fn abs(self) -> $t {
+				$t::abs(self)
+			}
+			fn min(self, other: $t) -> $t {
+				$t::min(self, other)
+			}
+			fn max(self, other: $t) -> $t {
+				$t::max(self, other)
+			}
+			fn floor(self) -> $t {
+				$t::floor(self)
+			}
+			fn signum(self) -> $t {
+				$t::signum(self)
+			}
+			fn is_finite(self) -> bool {
+				$t::is_finite(self)
+			}
+
+			fn sin(self) -> $t {
+				$t::sin(self)
+			}
+			fn cos(self) -> $t {
+				$t::cos(self)
+			}
+			fn tan(self) -> $t {
+				$t::tan(self)
+			}
+			fn sin_cos(self) -> ($t, $t) {
+				$t::sin_cos(self)
+			}
+			fn asin(self) -> $t {
+				$t::asin(self)
+			}
+			fn acos(self) -> $t {
+				$t::acos(self)
+			}
+			fn atan2(self, other: $t) -> $t {
+				$t::atan2(self, other)
+			}
+		}
+	};
+}
+
+impl_scalar!(f32);
+impl_scalar!(f64);
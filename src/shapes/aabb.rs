@@ -0,0 +1,368 @@
+use std::rc::Rc;
+use std::sync::Arc;
+
+use matrix::Mat4;
+use shapes::Triangle;
+use vector::Vec3;
+use Scalar;
+
+/// A Trait for Objects with a finite extent that can be enclosed in an [Aabb](struct.Aabb.html)
+pub trait Bounded<T: Scalar = f32> {
+	/// get the smallest axis-aligned box that contains the whole Object
+	fn bounds(&self) -> Aabb<T>;
+}
+
+impl<T: Scalar, B: Bounded<T> + ?Sized> Bounded<T> for &B {
+	fn bounds(&self) -> Aabb<T> {
+		(**self).bounds()
+	}
+}
+
+impl<T: Scalar, B: Bounded<T> + ?Sized> Bounded<T> for Box<B> {
+	fn bounds(&self) -> Aabb<T> {
+		(**self).bounds()
+	}
+}
+
+impl<T: Scalar, B: Bounded<T> + ?Sized> Bounded<T> for Rc<B> {
+	fn bounds(&self) -> Aabb<T> {
+		(**self).bounds()
+	}
+}
+
+impl<T: Scalar, B: Bounded<T> + ?Sized> Bounded<T> for Arc<B> {
+	fn bounds(&self) -> Aabb<T> {
+		(**self).bounds()
+	}
+}
+
+/// An axis-aligned bounding box, containing all Points between `min` and `max`
+///
+/// A box with any Component of `min` greater than the one of `max` is [empty](#method.empty).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Aabb<T: Scalar = f32> {
+	/// The corner with the smallest Components
+	pub min: Vec3<T>,
+	/// The corner with the largest Components
+	pub max: Vec3<T>,
+}
+
+impl<T: Scalar> Aabb<T> {
+	/// creates a new box between two corners
+	///
+	/// The corners don't have to be ordered, the Components are sorted automatically
+	pub fn new(a: Vec3<T>, b: Vec3<T>) -> Aabb<T> {
+		Aabb {
+			min: a.min(b),
+			max: a.max(b),
+		}
+	}
+	/// creates an empty box that contains nothing
+	///
+	/// This is the neutral element of [`union`](#method.union), so that any box or Point added
+	/// to it results in just that box or Point.
+	pub fn empty() -> Aabb<T> {
+		let inf = Vec3 {
+			x: T::INFINITY,
+			y: T::INFINITY,
+			z: T::INFINITY,
+		};
+		Aabb { min: inf, max: -inf }
+	}
+	/// creates the smallest box that contains all the Points
+	///
+	/// returns an [empty](#method.empty) box if there are no Points
+	pub fn from_points<I: IntoIterator<Item = Vec3<T>>>(points: I) -> Aabb<T> {
+		points.into_iter().fold(Aabb::empty(), Aabb::include)
+	}
+	/// creates the smallest box that contains the Triangle
+	pub fn from_triangle(triangle: &Triangle<T>) -> Aabb<T> {
+		Aabb::from_points(triangle.corners.iter().cloned())
+	}
+	/// creates the smallest box that contains all the Triangles
+	///
+	/// returns an [empty](#method.empty) box if there are no Triangles
+	pub fn from_triangles<'a, I>(triangles: I) -> Aabb<T>
+	where
+		I: IntoIterator<Item = &'a Triangle<T>>,
+		T: 'a,
+	{
+		Aabb::from_points(triangles.into_iter().flat_map(|t| t.corners.iter().cloned()))
+	}
+	/// checks if the box contains nothing
+	pub fn is_empty(&self) -> bool {
+		self.min.x > self.max.x || self.min.y > self.max.y || self.min.z > self.max.z
+	}
+	/// Returns the smallest box that contains both boxes
+	pub fn union(self, other: Aabb<T>) -> Aabb<T> {
+		Aabb {
+			min: self.min.min(other.min),
+			max: self.max.max(other.max),
+		}
+	}
+	/// Returns the region that is inside of both boxes
+	///
+	/// returns None if the boxes don't overlap
+	pub fn intersection(self, other: Aabb<T>) -> Option<Aabb<T>> {
+		let result = Aabb {
+			min: self.min.max(other.min),
+			max: self.max.min(other.max),
+		};
+		if result.is_empty() {
+			None
+		} else {
+			Some(result)
+		}
+	}
+	/// Returns the smallest box that contains both the box and `point`
+	pub fn include(self, point: Vec3<T>) -> Aabb<T> {
+		Aabb {
+			min: self.min.min(point),
+			max: self.max.max(point),
+		}
+	}
+	/// Returns the box grown by `margin` in every direction
+	///
+	/// A negative `margin` shrinks the box, which may result in an empty box
+	pub fn expand(self, margin: T) -> Aabb<T> {
+		let margin = Vec3 {
+			x: margin,
+			y: margin,
+			z: margin,
+		};
+		Aabb {
+			min: self.min - margin,
+			max: self.max + margin,
+		}
+	}
+	/// calculates the center of the box
+	pub fn center(&self) -> Vec3<T> {
+		(self.min + self.max) / T::TWO
+	}
+	/// calculates the size of the box along each Axis
+	pub fn extents(&self) -> Vec3<T> {
+		self.max - self.min
+	}
+	/// calculates the surface area of the box
+	///
+	/// returns 0 for an empty box
+	pub fn surface_area(&self) -> T {
+		if self.is_empty() {
+			return T::ZERO;
+		}
+		let e = self.extents();
+		T::TWO * (e.x * e.y + e.y * e.z + e.z * e.x)
+	}
+	/// calculates the volume of the box
+	///
+	/// returns 0 for an empty box
+	pub fn volume(&self) -> T {
+		if self.is_empty() {
+			return T::ZERO;
+		}
+		let e = self.extents();
+		e.x * e.y * e.z
+	}
+	/// Returns the eight corners of the box
+	///
+	/// Bit 0, 1 and 2 of the index select `max` instead of `min` for the x, y and z Component
+	pub fn corners(&self) -> [Vec3<T>; 8] {
+		let mut corners = [self.min; 8];
+		for (i, corner) in corners.iter_mut().enumerate() {
+			for axis in 0..3 {
+				if i & (1 << axis) != 0 {
+					corner[axis] = self.max[axis];
+				}
+			}
+		}
+		corners
+	}
+	/// checks if the point is within the box (including the surface)
+	pub fn contains(&self, point: Vec3<T>) -> bool {
+		self.min.x <= point.x
+			&& point.x <= self.max.x
+			&& self.min.y <= point.y
+			&& point.y <= self.max.y
+			&& self.min.z <= point.z
+			&& point.z <= self.max.z
+	}
+	/// checks if the two boxes overlap (including touching surfaces)
+	pub fn overlaps(&self, other: &Aabb<T>) -> bool {
+		self.min.x <= other.max.x
+			&& other.min.x <= self.max.x
+			&& self.min.y <= other.max.y
+			&& other.min.y <= self.max.y
+			&& self.min.z <= other.max.z
+			&& other.min.z <= self.max.z
+	}
+	/// finds the Point within the box that is closest to `point`
+	///
+	/// returns `point` itself if it is inside of the box
+	pub fn closest_point(&self, point: Vec3<T>) -> Vec3<T> {
+		point.max(self.min).min(self.max)
+	}
+	/// Returns the smallest box that contains this box after being transformed by `matrix`
+	///
+	/// All eight corners are transformed, so this works for projective Matrices as well. The
+	/// result is usually larger than the transformed Object itself, since rotating a box adds
+	/// empty space around it.
+	pub fn transform(&self, matrix: &Mat4<T>) -> Aabb<T> {
+		if self.is_empty() {
+			return *self;
+		}
+		Aabb::from_points(self.corners().iter().map(|&c| matrix.transform_point(c)))
+	}
+}
+
+impl<T: Scalar> Bounded<T> for Aabb<T> {
+	fn bounds(&self) -> Aabb<T> {
+		*self
+	}
+}
+
+use ray_tracing::*;
+
+impl<T: Scalar> Aabb<T> {
+	/// Intersects the line of the Ray with the box using the slab method
+	///
+	/// Returns the parametric distances where the line enters and leaves the box. The entry may be
+	/// smaller than `ray.t_min` if the Ray starts inside of the box.
+	///
+	/// returns None if the box is missed or only hit outside of `[ray.t_min, ray.t_max]`
+	pub fn intersect_ray(&self, ray: &Ray<T>) -> Option<(T, T)> {
+		self.slab(ray).map(|(entry, _, exit, _)| (entry, exit))
+	}
+	/// Returns the entry and exit distances together with the Axis of the slab that was crossed
+	fn slab(&self, ray: &Ray<T>) -> Option<(T, usize, T, usize)> {
+		let (mut entry, mut entry_axis) = (-T::INFINITY, 0);
+		let (mut exit, mut exit_axis) = (T::INFINITY, 0);
+		for axis in 0..3 {
+			let inverse = T::ONE / ray.direction[axis];
+			let mut near = (self.min[axis] - ray.start[axis]) * inverse;
+			let mut far = (self.max[axis] - ray.start[axis]) * inverse;
+			if inverse < T::ZERO {
+				std::mem::swap(&mut near, &mut far);
+			}
+			// comparisons with NaN (a Ray parallel to and exactly on a slab border) are always
+			// false, which ignores that slab
+			if near > entry {
+				entry = near;
+				entry_axis = axis;
+			}
+			if far < exit {
+				exit = far;
+				exit_axis = axis;
+			}
+		}
+		if entry > exit || exit < ray.t_min || entry > ray.t_max {
+			None
+		} else {
+			Some((entry, entry_axis, exit, exit_axis))
+		}
+	}
+}
+
+impl<T: Scalar> RayTarget<T> for Aabb<T> {
+	/// Intersects the Ray with the box using the slab method
+	///
+	/// If the Ray starts inside of the box, the exit Point is returned. The normal always points outwards.
+	fn hit_info(&self, ray: &Ray<T>) -> Option<HitInfo<T>> {
+		let (entry, entry_axis, exit, exit_axis) = self.slab(ray)?;
+		let (distance, axis, sign) = if ray.accepts(entry) {
+			(entry, entry_axis, -T::ONE)
+		} else if ray.accepts(exit) {
+			(exit, exit_axis, T::ONE)
+		} else {
+			return None;
+		};
+		let mut normal = Vec3::new();
+		normal[axis] = sign * ray.direction[axis].signum();
+		Some(HitInfo {
+			point: ray.at(distance),
+			distance,
+			normal,
+			..Default::default()
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use vector::Vector;
+
+	fn unit() -> Aabb {
+		Aabb::new(Vector::from((1.0, 1.0, 1.0)), Vector::from((-1.0, -1.0, -1.0)))
+	}
+
+	#[test]
+	fn aabb_construction() {
+		let b = unit();
+		assert_eq!(b.min, Vector::from((-1.0, -1.0, -1.0)));
+		assert!(Aabb::<f32>::empty().is_empty());
+		assert!(Aabb::<f32>::from_points(vec![]).is_empty());
+
+		let t = Triangle::new(Vector::from((0.0, 2.0, -1.0)), Vector::from((3.0, 0.0, 0.0)), Vector::from((1.0, 1.0, 4.0)));
+		let tb = Aabb::from_triangle(&t);
+		assert_eq!(tb, Aabb::new(Vector::from((0.0, 0.0, -1.0)), Vector::from((3.0, 2.0, 4.0))));
+		assert_eq!(Aabb::from_triangles(&[t, t]), tb);
+
+		assert_eq!(Aabb::empty().union(b), b);
+		assert_eq!(b.union(tb), Aabb::new(Vector::from((-1.0, -1.0, -1.0)), Vector::from((3.0, 2.0, 4.0))));
+		assert_eq!(b.intersection(tb), Some(Aabb::new(Vector::from((0.0, 0.0, -1.0)), Vector::from((1.0, 1.0, 1.0)))));
+		assert_eq!(b.intersection(tb.expand(-2.0)), None);
+		assert_eq!(b.include(Vector::from((0.0, 5.0, 0.0))).max, Vector::from((1.0, 5.0, 1.0)));
+		assert_eq!(b.expand(1.0).extents(), Vector::from((4.0, 4.0, 4.0)));
+	}
+
+	#[test]
+	fn aabb_measures() {
+		let b = Aabb::new(Vector::from((1.0, 2.0, 3.0)), Vector::from((2.0, 4.0, 6.0)));
+		assert_eq!(b.center(), Vector::from((1.5, 3.0, 4.5)));
+		assert_eq!(b.extents(), Vector::from((1.0, 2.0, 3.0)));
+		assert!((b.surface_area() - 22.0).abs() <= f32::EPSILON);
+		assert!((b.volume() - 6.0).abs() <= f32::EPSILON);
+		assert_eq!(Aabb::<f32>::empty().volume(), 0.0);
+		assert_eq!(b.corners()[0], b.min);
+		assert_eq!(b.corners()[7], b.max);
+		assert_eq!(b.corners()[5], Vector::from((2.0, 2.0, 6.0)));
+
+		assert!(b.contains(Vector::from((1.0, 3.0, 6.0))));
+		assert!(!b.contains(Vector::from((0.9, 3.0, 6.0))));
+		assert_eq!(b.closest_point(Vector::from((0.0, 3.0, 9.0))), Vector::from((1.0, 3.0, 6.0)));
+		assert!(b.overlaps(&Aabb::new(Vector::new(), Vector::from((1.0, 2.0, 3.0)))));
+		assert!(!b.overlaps(&unit()));
+	}
+
+	#[test]
+	fn aabb_transform() {
+		let b = unit().transform(&(Mat4::translate(Vector::from((5.0, 0.0, 0.0))) * Mat4::rot_z(std::f32::consts::PI / 4.0)));
+		let s = 2.0f32.sqrt();
+		assert_eq!(b.min, Vector::from((5.0 - s, -s, -1.0)));
+		assert_eq!(b.max, Vector::from((5.0 + s, s, 1.0)));
+		assert!(Aabb::empty().transform(&Mat4::scale_uniform(2.0)).is_empty());
+	}
+
+	#[test]
+	fn aabb_hit_info() {
+		let b = unit();
+		let ray = Ray::new(Vector::from((-5.0, 0.5, 0.0)), Vector::from((1.0, 0.0, 0.0)));
+		assert_eq!(b.intersect_ray(&ray), Some((4.0, 6.0)));
+		let hit = b.hit_info(&ray).unwrap();
+		assert_eq!(hit.point, Vector::from((-1.0, 0.5, 0.0)));
+		assert_eq!(hit.normal, Vector::from((-1.0, 0.0, 0.0)));
+
+		// starting inside of the box hits the exit Point
+		let inside = Ray::new(Vector::new(), Vector::from((0.0, 0.0, -1.0)));
+		assert_eq!(b.intersect_ray(&inside), Some((-1.0, 1.0)));
+		let hit = b.hit_info(&inside).unwrap();
+		assert!((hit.distance - 1.0).abs() <= f32::EPSILON);
+		assert_eq!(hit.normal, Vector::from((0.0, 0.0, -1.0)));
+
+		assert!(!b.hits(&Ray::new(ray.start, -ray.direction)));
+		assert!(!b.hits(&Ray::new(Vector::from((-5.0, 1.5, 0.0)), ray.direction)));
+		assert!(!b.hits(&ray.with_interval(0.0, 3.5)));
+		assert!(b.hits(&ray.with_interval(5.0, 10.0)));
+		assert!(b.hits(&Ray::new(Vector::from((-5.0, -5.0, 0.0)), Vector::from((1.0, 1.0, 0.0)))));
+	}
+}
@@ -0,0 +1,372 @@
+use shapes::{Aabb, Bounded, Triangle};
+use vector::Vec3;
+use vector2::Vec2;
+use Scalar;
+
+/// A Triangle Mesh where the faces share their vertices
+///
+/// Every face is a list of three indices into the vertex attributes. The optional `normals` and
+/// `uvs` have one entry per vertex, just like `positions`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Mesh<T: Scalar = f32> {
+	/// The positions of the vertices
+	pub positions: Vec<Vec3<T>>,
+	/// The normals of the vertices (_optional_)
+	pub normals: Option<Vec<Vec3<T>>>,
+	/// The texture coordinates of the vertices (_optional_)
+	pub uvs: Option<Vec<Vec2<T>>>,
+	/// The faces as indices of their corners, in counter-clockwise order
+	pub faces: Vec<[u32; 3]>,
+}
+
+impl<T: Scalar> Mesh<T> {
+	/// creates a new Mesh from the vertex positions and faces, without normals or texture coordinates
+	pub fn new(positions: Vec<Vec3<T>>, faces: Vec<[u32; 3]>) -> Mesh<T> {
+		Mesh {
+			positions,
+			normals: None,
+			uvs: None,
+			faces,
+		}
+	}
+	/// creates a Mesh from a list of Triangles, merging corners that are exactly equal
+	pub fn from_triangles(triangles: &[Triangle<T>]) -> Mesh<T> {
+		let mut mesh = Mesh::new(vec![], Vec::with_capacity(triangles.len()));
+		let mut lookup = ::std::collections::HashMap::new();
+		for triangle in triangles {
+			let mut face = [0; 3];
+			for (index, corner) in face.iter_mut().zip(triangle.corners.iter()) {
+				let key = (corner.x.to_f64().to_bits(), corner.y.to_f64().to_bits(), corner.z.to_f64().to_bits());
+				let positions = &mut mesh.positions;
+				*index = *lookup.entry(key).or_insert_with(|| {
+					positions.push(*corner);
+					positions.len() as u32 - 1
+				});
+			}
+			mesh.faces.push(face);
+		}
+		mesh
+	}
+	/// Returns the number of vertices
+	pub fn vertex_count(&self) -> usize {
+		self.positions.len()
+	}
+	/// Returns the number of faces
+	pub fn face_count(&self) -> usize {
+		self.faces.len()
+	}
+	/// creates the Triangle of the face at `index`
+	///
+	/// # Panics
+	/// Panics if `index` or any of the indices of the face are out of bounds
+	pub fn triangle(&self, index: usize) -> Triangle<T> {
+		let [a, b, c] = self.faces[index];
+		Triangle::new(self.positions[a as usize], self.positions[b as usize], self.positions[c as usize])
+	}
+	/// Returns an Iterator over the Triangles of all faces
+	///
+	/// # Panics
+	/// The Iterator panics if a face references a vertex that doesn't exist, see [is_valid](#method.is_valid)
+	pub fn triangles(&self) -> impl Iterator<Item = Triangle<T>> + '_ {
+		(0..self.faces.len()).map(move |i| self.triangle(i))
+	}
+	/// checks if all faces only reference existing vertices and all attributes have one entry per vertex
+	pub fn is_valid(&self) -> bool {
+		let count = self.positions.len();
+		self.normals.as_ref().is_none_or(|n| n.len() == count)
+			&& self.uvs.as_ref().is_none_or(|uv| uv.len() == count)
+			&& self.faces.iter().all(|face| face.iter().all(|&i| (i as usize) < count))
+	}
+	/// calculates the normal of each vertex as the average of the normals of the adjacent faces,
+	/// weighted by the area of the faces
+	///
+	/// Vertices that are not part of any (non-degenerate) face get a zero Vector.
+	///
+	/// # Panics
+	/// Panics if a face references a vertex that doesn't exist, see [is_valid](#method.is_valid)
+	pub fn vertex_normals(&self) -> Vec<Vec3<T>> {
+		let mut normals = vec![Vec3::new(); self.positions.len()];
+		for face in &self.faces {
+			let [a, b, c] = [face[0] as usize, face[1] as usize, face[2] as usize];
+			// the length of the cross product is twice the area of the face
+			let weighted = (self.positions[b] - self.positions[a]).cross(self.positions[c] - self.positions[a]);
+			normals[a] += weighted;
+			normals[b] += weighted;
+			normals[c] += weighted;
+		}
+		for normal in normals.iter_mut() {
+			if normal.length_sq() > T::ZERO {
+				*normal = normal.norm();
+			}
+		}
+		normals
+	}
+	/// replaces the `normals` with the area-weighted [vertex normals](#method.vertex_normals)
+	///
+	/// # Panics
+	/// Panics if a face references a vertex that doesn't exist, see [is_valid](#method.is_valid)
+	pub fn compute_normals(&mut self) {
+		self.normals = Some(self.vertex_normals());
+	}
+	/// calculates the total area of all faces
+	///
+	/// # Panics
+	/// Panics if a face references a vertex that doesn't exist, see [is_valid](#method.is_valid)
+	pub fn surface_area(&self) -> T {
+		self.triangles().map(|t| t.area()).sum()
+	}
+	/// calculates the volume enclosed by the Mesh
+	///
+	/// The result is only meaningful if the Mesh is closed. It is positive if the faces are
+	/// oriented counter-clockwise when viewed from the outside, and negative otherwise.
+	///
+	/// # Panics
+	/// Panics if a face references a vertex that doesn't exist, see [is_valid](#method.is_valid)
+	pub fn volume(&self) -> T {
+		// sum of the signed volumes of the tetrahedra between the origin and each face
+		let six = T::from_f64(6.0);
+		self.triangles().map(|t| t[0] * t[1].cross(t[2]) / six).sum()
+	}
+	/// turns the Mesh into an [AcceleratedMesh](struct.AcceleratedMesh.html) for faster Raycasting
+	///
+	/// # Panics
+	/// Panics if the Mesh [is not valid](#method.is_valid)
+	pub fn accelerate(self) -> AcceleratedMesh<T> {
+		AcceleratedMesh::new(self)
+	}
+	/// fills in the interpolated vertex normal and texture coordinates of a hit on the face at `index`
+	///
+	/// The Mesh has to be [valid](#method.is_valid)
+	fn complete_hit(&self, index: usize, mut hit: HitInfo<T>) -> HitInfo<T> {
+		let weights = match hit.barycentric {
+			Some(weights) => weights,
+			None => return hit,
+		};
+		let face = self.faces[index];
+		if let Some(ref normals) = self.normals {
+			let normal = (0..3).fold(Vec3::new(), |acc, i| acc + normals[face[i] as usize] * weights[i]);
+			if normal.length_sq() > T::ZERO {
+				hit.normal = normal.norm();
+			}
+		}
+		if let Some(ref uvs) = self.uvs {
+			hit.uv = Some((0..3).fold(Vec2::new(), |acc, i| acc + uvs[face[i] as usize] * weights[i]));
+		}
+		hit
+	}
+}
+
+impl<T: Scalar> Bounded<T> for Mesh<T> {
+	fn bounds(&self) -> Aabb<T> {
+		Aabb::from_points(self.positions.iter().cloned())
+	}
+}
+
+use ray_tracing::*;
+
+impl<T: Scalar> Mesh<T> {
+	/// finds the closest hit of the Ray with any face by testing every face
+	///
+	/// returns the index of the hit face together with the hit, or None if nothing is hit or
+	/// the Mesh [is not valid](#method.is_valid)
+	pub fn nearest_hit(&self, ray: &Ray<T>) -> Option<(usize, HitInfo<T>)> {
+		if !self.is_valid() {
+			return None;
+		}
+		let mut ray = *ray;
+		let mut closest = None;
+		for (index, triangle) in self.triangles().enumerate() {
+			if let Some(hit) = triangle.hit_info(&ray) {
+				ray.t_max = hit.distance;
+				closest = Some((index, hit));
+			}
+		}
+		closest.map(|(index, hit)| (index, self.complete_hit(index, hit)))
+	}
+}
+
+impl<T: Scalar> RayTarget<T> for Mesh<T> {
+	/// Intersects the Ray with every face of the Mesh
+	///
+	/// If the Mesh has vertex normals or texture coordinates, they are interpolated at the hit.
+	/// Use [accelerate](#method.accelerate) for large Meshes. A Mesh that [is not valid](#method.is_valid)
+	/// is never hit.
+	fn hit_info(&self, ray: &Ray<T>) -> Option<HitInfo<T>> {
+		self.nearest_hit(ray).map(|(_, hit)| hit)
+	}
+	fn hits(&self, ray: &Ray<T>) -> bool {
+		self.is_valid() && self.triangles().any(|t| t.hits(ray))
+	}
+}
+
+/// A Mesh together with a [Bvh](../ray_tracing/struct.Bvh.html) over its faces
+///
+/// The Bvh only stores the indices of the faces, so the vertices exist only once in the Mesh.
+/// It is built once on creation, so the Mesh can not be modified afterwards.
+#[derive(Clone, Debug)]
+pub struct AcceleratedMesh<T: Scalar = f32> {
+	mesh: Mesh<T>,
+	bvh: Bvh<u32, T>,
+}
+
+impl<T: Scalar> AcceleratedMesh<T> {
+	/// builds the Bvh for the Mesh
+	///
+	/// # Panics
+	/// Panics if the Mesh [is not valid](struct.Mesh.html#method.is_valid) or has more than `u32::MAX` faces
+	pub fn new(mesh: Mesh<T>) -> AcceleratedMesh<T> {
+		assert!(mesh.is_valid(), "a face references a vertex that doesn't exist or the vertex attributes have different lengths");
+		assert!(mesh.faces.len() <= u32::MAX as usize, "too many faces for an AcceleratedMesh");
+		let faces = (0..mesh.faces.len() as u32).collect();
+		let bvh = Bvh::with_bounds(faces, |&face| {
+			let corners = mesh.faces[face as usize].iter().map(|&i| mesh.positions[i as usize]);
+			Aabb::from_points(corners)
+		});
+		AcceleratedMesh { mesh, bvh }
+	}
+	/// Returns the underlying Mesh
+	pub fn mesh(&self) -> &Mesh<T> {
+		&self.mesh
+	}
+	/// Returns the underlying Mesh, destroying the Bvh
+	pub fn into_mesh(self) -> Mesh<T> {
+		self.mesh
+	}
+	/// finds the closest hit of the Ray with any face
+	///
+	/// returns the index of the hit face together with the hit, or None if nothing is hit
+	pub fn nearest_hit(&self, ray: &Ray<T>) -> Option<(usize, HitInfo<T>)> {
+		self.bvh
+			.nearest_hit_with(ray, |&face, ray| self.mesh.triangle(face as usize).hit_info(ray))
+			.map(|(index, hit)| (index, self.mesh.complete_hit(index, hit)))
+	}
+}
+
+impl<T: Scalar> Bounded<T> for AcceleratedMesh<T> {
+	fn bounds(&self) -> Aabb<T> {
+		self.bvh.bounds()
+	}
+}
+
+impl<T: Scalar> RayTarget<T> for AcceleratedMesh<T> {
+	fn hit_info(&self, ray: &Ray<T>) -> Option<HitInfo<T>> {
+		self.nearest_hit(ray).map(|(_, hit)| hit)
+	}
+	fn hits(&self, ray: &Ray<T>) -> bool {
+		self.bvh.any_hit_with(ray, |&face, ray| self.mesh.triangle(face as usize).hits(ray))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use vector::Vector;
+
+	/// a cube from (0, 0, 0) to (2, 2, 2) with outwards facing faces
+	fn cube() -> Mesh {
+		let positions = (0..8)
+			.map(|i| Vector::from(((i & 1) as f32 * 2.0, (i >> 1 & 1) as f32 * 2.0, (i >> 2 & 1) as f32 * 2.0)))
+			.collect();
+		let faces = vec![
+			[0, 2, 1], [1, 2, 3], // -z
+			[4, 5, 6], [5, 7, 6], // +z
+			[0, 1, 4], [1, 5, 4], // -y
+			[2, 6, 3], [3, 6, 7], // +y
+			[0, 4, 2], [2, 4, 6], // -x
+			[1, 3, 5], [3, 7, 5], // +x
+		];
+		Mesh::new(positions, faces)
+	}
+
+	#[test]
+	fn mesh_measures() {
+		let mesh = cube();
+		assert!(mesh.is_valid());
+		assert_eq!(mesh.vertex_count(), 8);
+		assert_eq!(mesh.face_count(), 12);
+		assert!((mesh.surface_area() - 24.0).abs() <= 1e-5);
+		assert!((mesh.volume() - 8.0).abs() <= 1e-5);
+		assert_eq!(mesh.bounds(), Aabb::new(Vector::new(), Vector::from((2.0, 2.0, 2.0))));
+		for triangle in mesh.triangles() {
+			// every face points away from the center
+			assert!(triangle.normal() * (triangle[0] - Vector::from((1.0, 1.0, 1.0))) > 0.0);
+		}
+
+		let mut flipped = mesh.clone();
+		flipped.faces.iter_mut().for_each(|f| f.swap(1, 2));
+		assert!((flipped.volume() + 8.0).abs() <= 1e-5);
+		flipped.faces.push([0, 1, 8]);
+		assert!(!flipped.is_valid());
+	}
+
+	#[test]
+	fn mesh_from_triangles() {
+		let mesh = cube();
+		let triangles: Vec<Triangle> = mesh.triangles().collect();
+		let welded = Mesh::from_triangles(&triangles);
+		assert_eq!(welded.vertex_count(), 8);
+		assert_eq!(welded.face_count(), 12);
+		assert!(welded.triangles().eq(triangles.into_iter()));
+	}
+
+	#[test]
+	fn mesh_normals() {
+		let mut mesh = cube();
+		mesh.compute_normals();
+		let corner = 1.0 / 3.0f32.sqrt();
+		assert_eq!(mesh.normals.as_ref().unwrap()[7], Vector::from((corner, corner, corner)));
+		assert_eq!(mesh.normals.as_ref().unwrap()[0], Vector::from((-corner, -corner, -corner)));
+
+		mesh.positions.push(Vector::new());
+		assert_eq!(mesh.vertex_normals()[8], Vector::new());
+	}
+
+	#[test]
+	fn mesh_hit_info() {
+		let mut mesh = cube();
+		let ray = Ray::new(Vector::from((0.5, 1.5, 10.0)), Vector::from((0.0, 0.0, -1.0)));
+		let (face, hit) = mesh.nearest_hit(&ray).unwrap();
+		assert!(face == 2 || face == 3);
+		assert!((hit.distance - 8.0).abs() <= 1e-5);
+		assert_eq!(hit.normal, Vector::from((0.0, 0.0, 1.0)));
+		assert!(hit.uv.is_none());
+		assert!(!mesh.hits(&Ray::new(ray.start, -ray.direction)));
+
+		mesh.compute_normals();
+		mesh.uvs = Some(mesh.positions.iter().map(|p| p.truncate() / 2.0).collect());
+		let hit = mesh.hit_info(&ray).unwrap();
+		assert_eq!(hit.uv, Some(Vec2 { x: 0.25, y: 0.75 }));
+		// the interpolated normal leans towards the -x and +y edges
+		assert!(hit.normal.x < 0.0 && hit.normal.y > 0.0 && hit.normal.z > 0.0);
+
+		let accelerated = mesh.clone().accelerate();
+		assert_eq!(accelerated.bounds(), mesh.bounds());
+		let (_, fast) = accelerated.nearest_hit(&ray).unwrap();
+		assert_eq!(fast.point, hit.point);
+		assert_eq!(fast.normal, hit.normal);
+		assert_eq!(fast.uv, hit.uv);
+		assert!(accelerated.hits(&Ray::new(Vector::from((1.0, 1.0, 1.0)), Vector::from((1.0, 0.3, 0.0)))));
+		assert_eq!(accelerated.into_mesh(), mesh);
+	}
+
+	#[test]
+	fn mesh_invalid_hit() {
+		let ray = Ray::new(Vector::from((0.5, 1.5, 10.0)), Vector::from((0.0, 0.0, -1.0)));
+		let mut mesh = cube();
+		mesh.faces.push([0, 1, 8]);
+		assert!(mesh.hit_info(&ray).is_none());
+		assert!(!mesh.hits(&ray));
+
+		let mut mesh = cube();
+		mesh.normals = Some(vec![Vector::new(); 3]);
+		assert!(mesh.hit_info(&ray).is_none());
+	}
+
+	#[test]
+	#[should_panic]
+	fn mesh_accelerate_invalid() {
+		let mut mesh = cube();
+		mesh.faces.push([0, 1, 8]);
+		mesh.accelerate();
+	}
+}
@@ -2,3 +2,21 @@
 
 mod triangle;
 pub use self::triangle::*;
+
+mod sphere;
+pub use self::sphere::*;
+
+mod plane;
+pub use self::plane::*;
+
+mod aabb;
+pub use self::aabb::*;
+
+mod obb;
+pub use self::obb::*;
+
+mod mesh;
+pub use self::mesh::*;
+
+mod point_cloud;
+pub use self::point_cloud::*;
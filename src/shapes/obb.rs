@@ -0,0 +1,415 @@
+use matrix::Mat4;
+use matrix3::Mat3;
+use shapes::{Aabb, Bounded, Triangle};
+use vector::Vec3;
+use Scalar;
+
+/// An oriented bounding box, a box that can be rotated arbitrarily
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Obb<T: Scalar = f32> {
+	/// The center of the box
+	pub center: Vec3<T>,
+	/// The local Axes of the box
+	///
+	/// should be normalized and perpendicular to each other, but is not guaranteed to be
+	pub axes: [Vec3<T>; 3],
+	/// Half of the size of the box along each of its `axes`
+	pub half_extents: Vec3<T>,
+}
+
+impl<T: Scalar> Obb<T> {
+	/// creates a new box with the given center, Axes and half extents
+	pub fn new(center: Vec3<T>, axes: [Vec3<T>; 3], half_extents: Vec3<T>) -> Obb<T> {
+		Obb {
+			center,
+			axes,
+			half_extents,
+		}
+	}
+	/// creates the box that results from transforming `aabb` by `matrix`
+	///
+	/// The Matrix should be affine and must not contain any shearing, otherwise the resulting
+	/// Axes are not perpendicular.
+	pub fn from_matrix(matrix: &Mat4<T>, aabb: &Aabb<T>) -> Obb<T> {
+		let half = aabb.extents() / T::TWO;
+		let mut axes = [Vec3::new(); 3];
+		let mut half_extents = Vec3::new();
+		for axis in 0..3 {
+			let mut local = Vec3::new();
+			local[axis] = T::ONE;
+			let v = matrix.transform_vector(local);
+			let length = v.length();
+			axes[axis] = v / length;
+			half_extents[axis] = half[axis] * length;
+		}
+		Obb {
+			center: matrix.transform_point(aabb.center()),
+			axes,
+			half_extents,
+		}
+	}
+	/// creates a box that tightly fits the Points by using the principal Axes of their distribution
+	///
+	/// The Axes are the eigenvectors of the covariance Matrix of the Points. This usually gives a
+	/// much tighter fit than an [Aabb](struct.Aabb.html), but is not guaranteed to be minimal.
+	///
+	/// returns None if there are no Points
+	pub fn fit(points: &[Vec3<T>]) -> Option<Obb<T>> {
+		if points.is_empty() {
+			return None;
+		}
+		let count = T::from_f64(points.len() as f64);
+		let mean = points.iter().cloned().sum::<Vec3<T>>() / count;
+		let mut covariance = Mat3::new();
+		for p in points {
+			let d = *p - mean;
+			for row in 0..3 {
+				for col in 0..3 {
+					covariance[row][col] += d[row] * d[col] / count;
+				}
+			}
+		}
+		let eigenvectors = jacobi_eigenvectors(covariance);
+		let x = Vec3::from((eigenvectors[0][0], eigenvectors[1][0], eigenvectors[2][0])).norm();
+		let y = Vec3::from((eigenvectors[0][1], eigenvectors[1][1], eigenvectors[2][1])).norm();
+		let axes = [x, y, x.cross(y)];
+
+		let local = Aabb::from_points(points.iter().map(|&p| {
+			let d = p - mean;
+			Vec3::from((d * axes[0], d * axes[1], d * axes[2]))
+		}));
+		let offset = local.center();
+		Some(Obb {
+			center: mean + axes[0] * offset.x + axes[1] * offset.y + axes[2] * offset.z,
+			axes,
+			half_extents: local.extents() / T::TWO,
+		})
+	}
+	/// calculates the volume of the box
+	pub fn volume(&self) -> T {
+		let h = self.half_extents;
+		T::TWO * T::TWO * T::TWO * h.x * h.y * h.z
+	}
+	/// converts a Point from world space into the local space of the box, where the box is
+	/// centered at the origin and aligned to the Axes
+	pub fn to_local(&self, point: Vec3<T>) -> Vec3<T> {
+		let d = point - self.center;
+		Vec3::from((d * self.axes[0], d * self.axes[1], d * self.axes[2]))
+	}
+	/// converts a Point from the local space of the box into world space
+	pub fn to_world(&self, local: Vec3<T>) -> Vec3<T> {
+		self.center + self.axes[0] * local.x + self.axes[1] * local.y + self.axes[2] * local.z
+	}
+	/// Returns the box in its local space
+	fn local_aabb(&self) -> Aabb<T> {
+		Aabb {
+			min: -self.half_extents,
+			max: self.half_extents,
+		}
+	}
+	/// Returns the eight corners of the box
+	///
+	/// Bit 0, 1 and 2 of the index select the positive side of the x, y and z Axis
+	pub fn corners(&self) -> [Vec3<T>; 8] {
+		let mut corners = self.local_aabb().corners();
+		for corner in corners.iter_mut() {
+			*corner = self.to_world(*corner);
+		}
+		corners
+	}
+	/// calculates the smallest axis-aligned box that contains this box
+	pub fn aabb(&self) -> Aabb<T> {
+		let mut extent = Vec3::new();
+		for axis in 0..3 {
+			for i in 0..3 {
+				extent[axis] += self.axes[i][axis].abs() * self.half_extents[i];
+			}
+		}
+		Aabb {
+			min: self.center - extent,
+			max: self.center + extent,
+		}
+	}
+	/// checks if the point is within the box (including the surface)
+	pub fn contains(&self, point: Vec3<T>) -> bool {
+		self.local_aabb().contains(self.to_local(point))
+	}
+	/// finds the Point within the box that is closest to `point`
+	///
+	/// returns `point` itself if it is inside of the box
+	pub fn closest_point(&self, point: Vec3<T>) -> Vec3<T> {
+		self.to_world(self.local_aabb().closest_point(self.to_local(point)))
+	}
+	/// checks if two boxes overlap using the separating axis theorem
+	pub fn overlaps(&self, other: &Obb<T>) -> bool {
+		let mut candidates = Vec::with_capacity(15);
+		candidates.extend_from_slice(&self.axes);
+		candidates.extend_from_slice(&other.axes);
+		for &a in &self.axes {
+			candidates.extend(other.axes.iter().filter_map(|&b| cross_axis(a, b)));
+		}
+		!candidates.into_iter().any(|axis| separates(self.project(axis), other.project(axis)))
+	}
+	/// checks if the box overlaps an axis-aligned box using the separating axis theorem
+	pub fn overlaps_aabb(&self, other: &Aabb<T>) -> bool {
+		self.overlaps(&Obb::from(*other))
+	}
+	/// checks if the box overlaps a Triangle using the separating axis theorem
+	pub fn overlaps_triangle(&self, triangle: &Triangle<T>) -> bool {
+		let edges = [
+			triangle[1] - triangle[0],
+			triangle[2] - triangle[1],
+			triangle[0] - triangle[2],
+		];
+		let mut candidates = Vec::with_capacity(13);
+		candidates.extend_from_slice(&self.axes);
+		candidates.extend(cross_axis(edges[0], edges[1]));
+		for &a in &self.axes {
+			candidates.extend(edges.iter().filter_map(|&e| cross_axis(a, e)));
+		}
+		!candidates.into_iter().any(|axis| {
+			let projected = triangle.corners.iter().map(|&c| c * axis);
+			let min = projected.clone().fold(T::INFINITY, T::min);
+			let max = projected.fold(-T::INFINITY, T::max);
+			separates(self.project(axis), (min, max))
+		})
+	}
+	/// projects the box onto `axis`, returning the covered interval
+	fn project(&self, axis: Vec3<T>) -> (T, T) {
+		let center = self.center * axis;
+		let radius = (0..3).fold(T::ZERO, |acc, i| acc + (self.axes[i] * axis).abs() * self.half_extents[i]);
+		(center - radius, center + radius)
+	}
+}
+
+/// checks if two intervals on a candidate Axis are disjoint
+fn separates<T: Scalar>(a: (T, T), b: (T, T)) -> bool {
+	a.1 < b.0 || b.1 < a.0
+}
+
+/// calculates the cross product of two edges as a candidate Axis for the separating axis theorem
+///
+/// returns None if the edges are (almost) parallel, since rounding errors would make the result
+/// meaningless. The threshold is relative to the lengths of the edges, so that the test works
+/// the same at any scale.
+fn cross_axis<T: Scalar>(a: Vec3<T>, b: Vec3<T>) -> Option<Vec3<T>> {
+	let axis = a.cross(b);
+	if axis.length_sq() > T::EPSILON * a.length_sq() * b.length_sq() {
+		Some(axis)
+	} else {
+		None
+	}
+}
+
+/// finds the eigenvectors of a symmetric Matrix using the Jacobi eigenvalue algorithm
+///
+/// The eigenvectors are returned as the columns of the resulting Matrix
+fn jacobi_eigenvectors<T: Scalar>(mut a: Mat3<T>) -> Mat3<T> {
+	let mut v = Mat3::identity();
+	for _ in 0..50 {
+		// rotate away the largest off-diagonal element
+		let (p, q) = [(0, 1), (0, 2), (1, 2)]
+			.iter()
+			.cloned()
+			.fold((0, 1), |best, (p, q)| {
+				if a[p][q].abs() > a[best.0][best.1].abs() {
+					(p, q)
+				} else {
+					best
+				}
+			});
+		let scale = a[0][0].abs() + a[1][1].abs() + a[2][2].abs();
+		if a[p][q].abs() <= T::EPSILON * scale || a[p][q] == T::ZERO {
+			break;
+		}
+		let theta = (a[q][q] - a[p][p]) / (T::TWO * a[p][q]);
+		let root = (theta * theta + T::ONE).sqrt();
+		let t = if theta >= T::ZERO {
+			T::ONE / (theta + root)
+		} else {
+			-T::ONE / (root - theta)
+		};
+		let c = T::ONE / (t * t + T::ONE).sqrt();
+		let s = t * c;
+		let mut jacobi = Mat3::identity();
+		jacobi[p][p] = c;
+		jacobi[p][q] = s;
+		jacobi[q][p] = -s;
+		jacobi[q][q] = c;
+		a = jacobi.transposed() * a * jacobi;
+		v *= jacobi;
+	}
+	v
+}
+
+impl<T: Scalar> From<Aabb<T>> for Obb<T> {
+	fn from(aabb: Aabb<T>) -> Obb<T> {
+		Obb {
+			center: aabb.center(),
+			axes: [
+				Vec3::from((T::ONE, T::ZERO, T::ZERO)),
+				Vec3::from((T::ZERO, T::ONE, T::ZERO)),
+				Vec3::from((T::ZERO, T::ZERO, T::ONE)),
+			],
+			half_extents: aabb.extents() / T::TWO,
+		}
+	}
+}
+
+impl<T: Scalar> Bounded<T> for Obb<T> {
+	fn bounds(&self) -> Aabb<T> {
+		self.aabb()
+	}
+}
+
+use ray_tracing::*;
+
+impl<T: Scalar> RayTarget<T> for Obb<T> {
+	/// Intersects the Ray with the box by transforming it into the local space of the box
+	///
+	/// If the Ray starts inside of the box, the exit Point is returned. The normal always points outwards.
+	fn hit_info(&self, ray: &Ray<T>) -> Option<HitInfo<T>> {
+		// the direction is not normalized again, so that distances stay the same
+		let local = Ray {
+			start: self.to_local(ray.start),
+			direction: Vec3::from((ray.direction * self.axes[0], ray.direction * self.axes[1], ray.direction * self.axes[2])),
+			..*ray
+		};
+		let hit = self.local_aabb().hit_info(&local)?;
+		let n = hit.normal;
+		Some(HitInfo {
+			point: ray.at(hit.distance),
+			distance: hit.distance,
+			normal: self.axes[0] * n.x + self.axes[1] * n.y + self.axes[2] * n.z,
+			..Default::default()
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use vector::Vector;
+
+	fn unit() -> Aabb {
+		Aabb::new(Vector::from((-1.0, -1.0, -1.0)), Vector::from((1.0, 1.0, 1.0)))
+	}
+
+	fn rotated() -> Obb {
+		// unit cube rotated by 45° around z and stretched along its local x Axis
+		let matrix = Mat4::translate(Vector::from((10.0, 0.0, 0.0))) * Mat4::rot_z(std::f32::consts::PI / 4.0) * Mat4::scale(Vector::from((2.0, 1.0, 1.0)));
+		Obb::from_matrix(&matrix, &unit())
+	}
+
+	fn assert_close(a: Vector, b: Vector) {
+		assert!((a - b).length() <= 1e-5, "{} != {}", a, b);
+	}
+
+	#[test]
+	fn obb_from_matrix() {
+		let b = rotated();
+		let s = 0.5f32.sqrt();
+		assert_eq!(b.center, Vector::from((10.0, 0.0, 0.0)));
+		assert_close(b.axes[0], Vector::from((s, s, 0.0)));
+		assert_close(b.axes[1], Vector::from((-s, s, 0.0)));
+		assert_close(b.half_extents, Vector::from((2.0, 1.0, 1.0)));
+		assert!((b.volume() - 16.0).abs() <= 1e-4);
+		assert_close(b.corners()[7], b.center + Vector::from((s, 3.0 * s, 1.0)));
+		assert_close(b.aabb().max, Vector::from((10.0 + 3.0 * s, 3.0 * s, 1.0)));
+		assert_eq!(Obb::from(unit()).aabb(), unit());
+
+		assert!(b.contains(b.center + b.axes[0] * 1.9));
+		assert!(!b.contains(b.center + b.axes[1] * 1.1));
+		assert_close(b.closest_point(b.center + b.axes[0] * 5.0), b.center + b.axes[0] * 2.0);
+		let p = Vector::from((1.0, 2.0, 3.0));
+		assert_close(b.to_world(b.to_local(p)), p);
+	}
+
+	#[test]
+	fn obb_fit() {
+		assert_eq!(Obb::<f32>::fit(&[]), None);
+		let matrix = Mat4::translate(Vector::from((1.0, 2.0, 3.0))) * Mat4::rotation(Vector::from((1.0, 1.0, 1.0)), 0.7) * Mat4::scale(Vector::from((3.0, 2.0, 1.0)));
+		let original = Obb::from_matrix(&matrix, &unit());
+		let fitted = Obb::fit(&original.corners()).unwrap();
+		assert_close(fitted.center, original.center);
+		assert!((fitted.volume() - original.volume()).abs() <= 1e-3);
+		// the Axes are sorted arbitrarily and their signs may be flipped
+		for axis in 0..3 {
+			let half_extent = original.half_extents[axis];
+			let i = (0..3).find(|&i| (fitted.half_extents[i] - half_extent).abs() <= 1e-4).unwrap();
+			let alignment: f32 = fitted.axes[i] * original.axes[axis];
+			assert!(alignment.abs() >= 1.0 - 1e-4);
+		}
+	}
+
+	#[test]
+	fn obb_overlaps() {
+		let b = rotated();
+		let s = 0.5f32.sqrt();
+		// the axis-aligned bounds overlap, but the box is separated along its diagonal face
+		let corner = Aabb::new(Vector::from((11.5, -1.5, -1.0)), Vector::from((12.5, -0.5, 1.0)));
+		assert!(b.aabb().overlaps(&corner));
+		assert!(!b.overlaps_aabb(&corner));
+		assert!(b.overlaps_aabb(&corner.expand(0.5)));
+
+		let moved = Obb { center: b.center + b.axes[1] * 2.01, ..b };
+		assert!(!b.overlaps(&moved));
+		let moved = Obb { center: b.center + b.axes[1] * 1.99, ..b };
+		assert!(b.overlaps(&moved));
+		assert!(b.overlaps(&Obb::from(b.aabb())));
+
+		// just above and parallel to the upper left face
+		let t = Triangle::new(
+			b.center + Vector::from((0.0, 2.0 * s + 0.1, 0.0)),
+			b.center + Vector::from((-10.0, 2.0 * s + 0.1 - 10.0, 0.0)),
+			b.center + Vector::from((-10.0, 10.0, 0.0)),
+		);
+		assert!(!b.overlaps_triangle(&t));
+		let t = Triangle::new(b.center, Vector::from((20.0, 10.0, 0.0)), Vector::from((0.0, 10.0, 0.0)));
+		assert!(b.overlaps_triangle(&t));
+		let t = Triangle::new(Vector::from((0.0, -5.0, 0.5)), Vector::from((20.0, -5.0, 0.5)), Vector::from((10.0, 5.0, 0.5)));
+		assert!(b.overlaps_triangle(&t));
+		let t = Triangle::new(Vector::from((0.0, -5.0, 1.5)), Vector::from((20.0, -5.0, 1.5)), Vector::from((10.0, 5.0, 1.5)));
+		assert!(!b.overlaps_triangle(&t));
+	}
+
+	#[test]
+	fn obb_overlaps_small_scale() {
+		// only separated along the Triangle normal, which has a tiny length at small scales
+		for &scale in &[1e-4f32, 1.0] {
+			let b = Obb::from(Aabb::new(Vector::from((-scale, -scale, -scale)), Vector::from((scale, scale, scale))));
+			let d = 3.5 * scale;
+			let t = Triangle::new(Vector::from((d, 0.0, 0.0)), Vector::from((0.0, d, 0.0)), Vector::from((0.0, 0.0, d)));
+			assert!(!b.overlaps_triangle(&t), "scale {}", scale);
+			let d = 2.5 * scale;
+			let t = Triangle::new(Vector::from((d, 0.0, 0.0)), Vector::from((0.0, d, 0.0)), Vector::from((0.0, 0.0, d)));
+			assert!(b.overlaps_triangle(&t), "scale {}", scale);
+
+			let touching = Obb {
+				center: Vector::from((2.0 * scale, 2.0 * scale, 0.0)),
+				..b
+			};
+			assert!(b.overlaps(&touching));
+			let moved = Obb { center: touching.center * 1.01, ..touching };
+			assert!(!b.overlaps(&moved));
+		}
+	}
+
+	#[test]
+	fn obb_hit_info() {
+		let b = rotated();
+		let s = 0.5f32.sqrt();
+		let ray = Ray::new(Vector::from((10.0, -10.0, 0.0)), Vector::from((0.0, 1.0, 0.0)));
+		let hit = b.hit_info(&ray).unwrap();
+		// the lower right face crosses x = 10 at y = -2s
+		assert!((hit.distance - (10.0 - 2.0 * s)).abs() <= 1e-4);
+		assert_close(hit.normal, Vector::from((s, -s, 0.0)));
+		assert!(!b.hits(&Ray::new(ray.start, -ray.direction)));
+		assert!(!b.hits(&Ray::new(Vector::from((14.0, -10.0, 0.0)), ray.direction)));
+
+		let inside = Ray::new(b.center, b.axes[0]);
+		let hit = b.hit_info(&inside).unwrap();
+		assert!((hit.distance - 2.0).abs() <= 1e-5);
+		assert_close(hit.normal, b.axes[0]);
+	}
+}
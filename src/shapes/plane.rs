@@ -0,0 +1,201 @@
+use shapes::Triangle;
+use vector::Vec3;
+use Scalar;
+
+/// An infinite Plane, defined by all Points `p` with `normal * p == d`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Plane<T: Scalar = f32> {
+	/// The normalized Normal of the Plane, pointing to the front side
+	pub normal: Vec3<T>,
+	/// The signed distance of the Plane from the origin along the normal
+	pub d: T,
+}
+
+/// The side of a Plane that a Point is on
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PlaneSide {
+	/// The side that the normal points to
+	Front,
+	/// The side opposite to the normal
+	Back,
+	/// Directly on the Plane
+	On,
+}
+
+impl<T: Scalar> Plane<T> {
+	/// creates a new Plane through `point` with the given `normal`
+	///
+	/// automatically normalizes the normal
+	pub fn new(point: Vec3<T>, normal: Vec3<T>) -> Plane<T> {
+		let normal = normal.norm();
+		Plane {
+			normal,
+			d: normal * point,
+		}
+	}
+	/// creates a new Plane through three Points
+	///
+	/// The normal points to the side where the Points appear counter-clockwise, which is the same
+	/// as the [normal of a Triangle](struct.Triangle.html#method.normal) with these corners.
+	///
+	/// returns None if the Points are on a line
+	pub fn from_points(a: Vec3<T>, b: Vec3<T>, c: Vec3<T>) -> Option<Plane<T>> {
+		let normal = (b - a).cross(c - a);
+		if normal.length_sq() == T::ZERO {
+			return None;
+		}
+		Some(Plane::new(a, normal))
+	}
+	/// creates the Plane that the Triangle lies on, with the same normal as the Triangle
+	///
+	/// returns None if the Triangle is degenerate
+	pub fn from_triangle(triangle: &Triangle<T>) -> Option<Plane<T>> {
+		Plane::from_points(triangle[0], triangle[1], triangle[2])
+	}
+	/// calculates the signed distance of `point` to the Plane
+	///
+	/// The distance is positive on the front side and negative on the back side
+	pub fn signed_distance(&self, point: Vec3<T>) -> T {
+		self.normal * point - self.d
+	}
+	/// finds the Point on the Plane that is closest to `point`
+	pub fn project(&self, point: Vec3<T>) -> Vec3<T> {
+		point - self.normal * self.signed_distance(point)
+	}
+	/// determines which side of the Plane `point` is on
+	///
+	/// Points within `tolerance` of the Plane are considered to be [on](enum.PlaneSide.html#variant.On) the Plane
+	pub fn side(&self, point: Vec3<T>, tolerance: T) -> PlaneSide {
+		let distance = self.signed_distance(point);
+		if distance > tolerance {
+			PlaneSide::Front
+		} else if distance < -tolerance {
+			PlaneSide::Back
+		} else {
+			PlaneSide::On
+		}
+	}
+	/// finds the single Point where three Planes intersect
+	///
+	/// returns None if any two of the Planes are parallel or all three share a line
+	pub fn intersect_planes(a: &Plane<T>, b: &Plane<T>, c: &Plane<T>) -> Option<Vec3<T>> {
+		let bc = b.normal.cross(c.normal);
+		let denominator = a.normal * bc;
+		if denominator == T::ZERO {
+			return None;
+		}
+		let ca = c.normal.cross(a.normal);
+		let ab = a.normal.cross(b.normal);
+		let point = (bc * a.d + ca * b.d + ab * c.d) / denominator;
+		if point.x.is_finite() && point.y.is_finite() && point.z.is_finite() {
+			Some(point)
+		} else {
+			None
+		}
+	}
+	/// calculates the parametric distance `t` where the infinite line `origin + direction * t` crosses the Plane
+	///
+	/// returns None if the line is parallel to the Plane
+	pub fn intersect_line_param(&self, origin: Vec3<T>, direction: Vec3<T>) -> Option<T> {
+		let denominator = self.normal * direction;
+		if denominator == T::ZERO {
+			return None;
+		}
+		Some(-self.signed_distance(origin) / denominator)
+	}
+	/// finds the Point where the infinite line through `origin` along `direction` crosses the Plane
+	///
+	/// returns None if the line is parallel to the Plane
+	pub fn intersect_line(&self, origin: Vec3<T>, direction: Vec3<T>) -> Option<Vec3<T>> {
+		self.intersect_line_param(origin, direction).map(|t| origin + direction * t)
+	}
+	/// finds the Point where the line segment from `start` to `end` crosses the Plane
+	///
+	/// returns None if both ends are on the same side of the Plane
+	pub fn intersect_segment(&self, start: Vec3<T>, end: Vec3<T>) -> Option<Vec3<T>> {
+		let direction = end - start;
+		match self.intersect_line_param(start, direction) {
+			Some(t) if T::ZERO <= t && t <= T::ONE => Some(start + direction * t),
+			_ => None,
+		}
+	}
+}
+
+use ray_tracing::*;
+
+impl<T: Scalar> RayTarget<T> for Plane<T> {
+	fn hit_info(&self, ray: &Ray<T>) -> Option<HitInfo<T>> {
+		let distance = self.intersect_line_param(ray.start, ray.direction)?;
+		if !ray.accepts(distance) {
+			return None;
+		}
+		Some(HitInfo {
+			point: ray.at(distance),
+			distance,
+			normal: self.normal,
+			..Default::default()
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use vector::Vector;
+
+	#[test]
+	fn plane_new() {
+		let p = Plane::new(Vector::from((0.0, 0.0, 3.0)), Vector::from((0.0, 0.0, 2.0)));
+		assert_eq!(p.normal, Vector::from((0.0, 0.0, 1.0)));
+		assert!((p.d - 3.0).abs() <= f32::EPSILON);
+
+		let a = Vector::from((1.0, 0.0, 3.0));
+		let b = Vector::from((0.0, 1.0, 3.0));
+		let c = Vector::from((0.0, 0.0, 3.0));
+		assert_eq!(Plane::from_points(c, a, b), Some(p));
+		assert_eq!(Plane::from_triangle(&Triangle::new(c, a, b)), Some(p));
+		assert_eq!(Plane::from_points(c, a, a * 2.0 - c), None);
+	}
+
+	#[test]
+	fn plane_distance() {
+		let p = Plane::new(Vector::from((1.0, 1.0, 1.0)), Vector::from((0.0, 1.0, 0.0)));
+		let point = Vector::from((5.0, -2.0, 7.0));
+		assert!((p.signed_distance(point) + 3.0).abs() <= f32::EPSILON);
+		assert_eq!(p.project(point), Vector::from((5.0, 1.0, 7.0)));
+		assert_eq!(p.side(point, 0.1), PlaneSide::Back);
+		assert_eq!(p.side(Vector::from((0.0, 2.0, 0.0)), 0.1), PlaneSide::Front);
+		assert_eq!(p.side(Vector::from((0.0, 1.05, 0.0)), 0.1), PlaneSide::On);
+	}
+
+	#[test]
+	fn plane_intersections() {
+		let x = Plane::new(Vector::from((1.0, 0.0, 0.0)), Vector::from((1.0, 0.0, 0.0)));
+		let y = Plane::new(Vector::from((0.0, 2.0, 0.0)), Vector::from((0.0, -1.0, 0.0)));
+		let z = Plane::new(Vector::from((0.0, 0.0, 3.0)), Vector::from((1.0, 1.0, 1.0)));
+		assert_eq!(Plane::intersect_planes(&x, &y, &z), Some(Vector::from((1.0, 2.0, 0.0))));
+		assert_eq!(Plane::intersect_planes(&x, &y, &x), None);
+
+		let origin = Vector::from((-1.0, 5.0, 5.0));
+		let direction = Vector::from((1.0, 0.0, 0.0));
+		assert_eq!(x.intersect_line(origin, direction), Some(Vector::from((1.0, 5.0, 5.0))));
+		assert_eq!(x.intersect_line(origin, -direction), Some(Vector::from((1.0, 5.0, 5.0))));
+		assert_eq!(y.intersect_line(origin, direction), None);
+
+		assert_eq!(x.intersect_segment(origin, origin + direction * 4.0), Some(Vector::from((1.0, 5.0, 5.0))));
+		assert_eq!(x.intersect_segment(origin, origin + direction), None);
+	}
+
+	#[test]
+	fn plane_hit_info() {
+		let p = Plane::new(Vector::from((0.0, 0.0, -2.0)), Vector::from((0.0, 0.0, 1.0)));
+		let ray = Ray::new(Vector::from((1.0, 1.0, 0.0)), Vector::from((0.0, 0.0, -1.0)));
+		let hit = p.hit_info(&ray).unwrap();
+		assert_eq!(hit.point, Vector::from((1.0, 1.0, -2.0)));
+		assert!((hit.distance - 2.0).abs() <= f32::EPSILON);
+		assert_eq!(hit.normal, p.normal);
+		assert!(!p.hits(&Ray::new(ray.start, -ray.direction)));
+		assert!(!p.hits(&ray.with_interval(0.0, 1.0)));
+		assert!(!p.hits(&Ray::new(ray.start, Vector::from((1.0, 0.0, 0.0)))));
+	}
+}
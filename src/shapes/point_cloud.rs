@@ -0,0 +1,64 @@
+use shapes::{Aabb, Bounded};
+use vector::Vec3;
+use Scalar;
+
+/// A set of Points without any connectivity, as produced by 3D scanners
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PointCloud<T: Scalar = f32> {
+	/// The positions of the Points
+	pub positions: Vec<Vec3<T>>,
+	/// The normals of the Points (_optional_)
+	pub normals: Option<Vec<Vec3<T>>>,
+	/// The colors of the Points as `[red, green, blue, alpha]` (_optional_)
+	pub colors: Option<Vec<[u8; 4]>>,
+}
+
+impl<T: Scalar> PointCloud<T> {
+	/// creates a new PointCloud from the positions, without normals or colors
+	pub fn new(positions: Vec<Vec3<T>>) -> PointCloud<T> {
+		PointCloud {
+			positions,
+			normals: None,
+			colors: None,
+		}
+	}
+	/// Returns the number of Points
+	pub fn len(&self) -> usize {
+		self.positions.len()
+	}
+	/// checks if there are no Points
+	pub fn is_empty(&self) -> bool {
+		self.positions.is_empty()
+	}
+	/// calculates the average position of all Points
+	///
+	/// returns None if there are no Points
+	pub fn centroid(&self) -> Option<Vec3<T>> {
+		if self.is_empty() {
+			return None;
+		}
+		let sum: Vec3<T> = self.positions.iter().cloned().sum();
+		Some(sum / T::from_f64(self.len() as f64))
+	}
+}
+
+impl<T: Scalar> Bounded<T> for PointCloud<T> {
+	fn bounds(&self) -> Aabb<T> {
+		Aabb::from_points(self.positions.iter().cloned())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use vector::Vector;
+
+	#[test]
+	fn point_cloud_measures() {
+		assert_eq!(PointCloud::<f32>::default().centroid(), None);
+		let cloud = PointCloud::new(vec![Vector::from((1.0, 0.0, 2.0)), Vector::from((-1.0, 4.0, 0.0))]);
+		assert_eq!(cloud.len(), 2);
+		assert_eq!(cloud.centroid(), Some(Vector::from((0.0, 2.0, 1.0))));
+		assert_eq!(cloud.bounds(), Aabb::new(Vector::from((-1.0, 0.0, 0.0)), Vector::from((1.0, 4.0, 2.0))));
+	}
+}
@@ -0,0 +1,177 @@
+use shapes::{Aabb, Bounded};
+use vector::Vec3;
+use vector2::Vec2;
+use Scalar;
+
+/// A Sphere with a center and a radius
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Sphere<T: Scalar = f32> {
+	/// The center of the Sphere
+	pub center: Vec3<T>,
+	/// The radius of the Sphere
+	pub radius: T,
+}
+
+impl<T: Scalar> Sphere<T> {
+	/// creates a new Sphere with the given center and radius
+	pub fn new(center: Vec3<T>, radius: T) -> Sphere<T> {
+		Sphere { center, radius }
+	}
+	/// calculates the volume of the Sphere
+	///
+	/// ```text
+	/// V = 4/3 * π * r^3
+	/// ```
+	pub fn volume(&self) -> T {
+		let r = self.radius;
+		T::from_f64(4.0 / 3.0) * T::PI * r * r * r
+	}
+	/// calculates the surface area of the Sphere
+	///
+	/// ```text
+	/// A = 4 * π * r^2
+	/// ```
+	pub fn surface_area(&self) -> T {
+		T::TWO * T::TWO * T::PI * self.radius * self.radius
+	}
+	/// checks if the point is within the Sphere (including the surface)
+	pub fn contains(&self, point: Vec3<T>) -> bool {
+		(point - self.center).length_sq() <= self.radius * self.radius
+	}
+	/// finds the Point within the Sphere that is closest to `point`
+	///
+	/// returns `point` itself if it is inside of the Sphere
+	pub fn closest_point(&self, point: Vec3<T>) -> Vec3<T> {
+		if self.contains(point) {
+			point
+		} else {
+			self.center + (point - self.center).norm() * self.radius
+		}
+	}
+	/// calculates the spherical texture coordinates for a Point on the surface with the (normalized) `normal`
+	///
+	/// u goes around the y Axis, starting and ending at -x. v goes from the bottom (-y) to the top (+y).
+	pub fn uv(&self, normal: Vec3<T>) -> Vec2<T> {
+		let half = T::ONE / T::TWO;
+		let clamped = normal.y.max(-T::ONE).min(T::ONE);
+		Vec2 {
+			x: half + normal.z.atan2(normal.x) / (T::TWO * T::PI),
+			y: half + clamped.asin() / T::PI,
+		}
+	}
+}
+
+impl<T: Scalar> Bounded<T> for Sphere<T> {
+	fn bounds(&self) -> Aabb<T> {
+		let r = self.radius.abs();
+		Aabb {
+			min: self.center - Vec3 { x: r, y: r, z: r },
+			max: self.center + Vec3 { x: r, y: r, z: r },
+		}
+	}
+}
+
+use ray_tracing::*;
+
+impl<T: Scalar> RayTarget<T> for Sphere<T> {
+	/// Intersects the Ray with the Sphere
+	///
+	/// Uses the numerically robust formulation from "Precision Improvements for Ray/Sphere
+	/// Intersection" (Ray Tracing Gems, Chapter 7). If the Ray starts inside of the Sphere,
+	/// the exit Point is returned. The normal always points outwards.
+	fn hit_info(&self, ray: &Ray<T>) -> Option<HitInfo<T>> {
+		let f = ray.start - self.center;
+		let a = ray.direction.length_sq();
+		let b = -(f * ray.direction);
+		// vector from the center to the closest Point on the line of the Ray. Using this instead
+		// of the textbook discriminant avoids catastrophic cancellation for distant Spheres
+		let l = f + ray.direction * (b / a);
+		let r2 = self.radius * self.radius;
+		let discriminant = a * (r2 - l.length_sq());
+		if discriminant < T::ZERO {
+			return None;
+		}
+		let c = f.length_sq() - r2;
+		let q = b + b.signum() * discriminant.sqrt();
+		let (t0, t1) = (c / q, q / a);
+		let (near, far) = if t0 <= t1 { (t0, t1) } else { (t1, t0) };
+
+		let distance = if ray.accepts(near) {
+			near
+		} else if ray.accepts(far) {
+			far
+		} else {
+			return None;
+		};
+		let point = ray.at(distance);
+		let normal = (point - self.center).norm();
+		Some(HitInfo {
+			point,
+			distance,
+			normal,
+			uv: Some(self.uv(normal)),
+			..Default::default()
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use vector::Vector;
+
+	#[test]
+	fn sphere_measures() {
+		let s = Sphere::new(Vector::from((1.0, 2.0, 3.0)), 2.0);
+		assert!((s.volume() - 32.0 / 3.0 * std::f32::consts::PI).abs() <= 1e-5);
+		assert!((s.surface_area() - 16.0 * std::f32::consts::PI).abs() <= 1e-5);
+		assert!(s.contains(Vector::from((1.0, 2.0, 5.0))));
+		assert!(!s.contains(Vector::from((1.0, 2.0, 5.1))));
+		assert_eq!(s.closest_point(Vector::from((1.0, 7.0, 3.0))), Vector::from((1.0, 4.0, 3.0)));
+		assert_eq!(s.closest_point(Vector::from((1.5, 2.0, 3.0))), Vector::from((1.5, 2.0, 3.0)));
+	}
+
+	#[test]
+	fn sphere_hit_info() {
+		let s = Sphere::new(Vector::from((0.0, 0.0, -5.0)), 1.0);
+		let ray = Ray::new(Vector::new(), Vector::from((0.0, 0.0, -1.0)));
+		let hit = s.hit_info(&ray).unwrap();
+		assert!((hit.distance - 4.0).abs() <= 1e-6);
+		assert_eq!(hit.point, Vector::from((0.0, 0.0, -4.0)));
+		assert_eq!(hit.normal, Vector::from((0.0, 0.0, 1.0)));
+
+		// starting inside of the Sphere hits the exit Point
+		let inside = Ray::new(s.center, Vector::from((0.0, 1.0, 0.0)));
+		let hit = s.hit_info(&inside).unwrap();
+		assert!((hit.distance - 1.0).abs() <= 1e-6);
+		assert_eq!(hit.normal, Vector::from((0.0, 1.0, 0.0)));
+
+		assert!(!s.hits(&Ray::new(Vector::new(), Vector::from((0.0, 0.0, 1.0)))));
+		assert!(!s.hits(&Ray::new(Vector::from((1.5, 0.0, 0.0)), Vector::from((0.0, 0.0, -1.0)))));
+		assert!(!s.hits(&ray.with_interval(0.0, 3.5)));
+		assert!(s.hits(&ray.with_interval(4.5, 10.0)));
+	}
+
+	#[test]
+	fn sphere_hit_far_away() {
+		let s = Sphere::new(Vector::from((0.0, 0.0, -10000.0)), 1.0);
+		let ray = Ray::new(Vector::from((0.0, 0.99, 0.0)), Vector::from((0.0, 0.0, -1.0)));
+		let hit = s.hit_info(&ray).unwrap();
+		assert!(((hit.point - s.center).length() - 1.0).abs() <= 1e-3);
+		assert!(!s.hits(&Ray::new(Vector::from((0.0, 1.01, 0.0)), ray.direction)));
+	}
+
+	#[test]
+	fn sphere_uv() {
+		let s = Sphere::new(Vector::new(), 1.0);
+		let uv = |x: f32, y: f32, z: f32| s.uv(Vector::from((x, y, z)));
+		assert!((uv(0.0, 1.0, 0.0).y - 1.0).abs() <= 1e-6);
+		assert!(uv(0.0, -1.0, 0.0).y.abs() <= 1e-6);
+		assert!((uv(1.0, 0.0, 0.0).x - 0.5).abs() <= 1e-6);
+		assert!((uv(0.0, 0.0, 1.0).x - 0.75).abs() <= 1e-6);
+		assert!((uv(1.0, 0.0, 0.0).y - 0.5).abs() <= 1e-6);
+
+		let hit = s.hit_info(&Ray::new(Vector::from((5.0, 0.0, 0.0)), Vector::from((-1.0, 0.0, 0.0)))).unwrap();
+		assert_eq!(hit.uv, Some(uv(1.0, 0.0, 0.0)));
+	}
+}
@@ -1,33 +1,35 @@
-use vector::Vector;
+use shapes::{Aabb, Bounded};
+use vector::Vec3;
+use Scalar;
 
 #[derive(Clone, Copy, Debug, PartialEq)]
-pub struct Triangle {
-	pub corners: [Vector; 3],
+pub struct Triangle<T: Scalar = f32> {
+	pub corners: [Vec3<T>; 3],
 }
 
-impl Triangle {
+impl<T: Scalar> Triangle<T> {
 	/// creates a new Triangle with the given corners
-	pub fn new(a: Vector, b: Vector, c: Vector) -> Triangle {
+	pub fn new(a: Vec3<T>, b: Vec3<T>, c: Vec3<T>) -> Triangle<T> {
 		Triangle { corners: [a, b, c] }
 	}
 	/// calculates the area of the Triangle
-	pub fn area(&self) -> f32 {
+	pub fn area(&self) -> T {
 		// area of Triangle is half the area of a Parallelogram with sides (ab, ac)
 		// area of Parallelogram = length of cross product of sides
-		0.5 * (self[1] - self[0]).cross(self[2] - self[0]).length()
+		(self[1] - self[0]).cross(self[2] - self[0]).length() / T::TWO
 	}
 	/// calculates a normalized Vector that is perpendicular to the Plane of the Triangle
-	pub fn normal(&self) -> Vector {
+	pub fn normal(&self) -> Vec3<T> {
 		(self[1] - self[0]).cross(self[2] - self[0]).norm()
 	}
 	/// checks if the point is within the Triangle
 	///
 	/// assumes that the Point is on the same Plane as the Triangle
-	pub fn contains(&self, point: Vector) -> bool {
+	pub fn contains(&self, point: Vec3<T>) -> bool {
 		let mut neg_count = 0;
 		for a in 0..2 {
 			for b in (a + 1)..3 {
-				if (self[a] - point) * (self[b] - point) <= 0.0 {
+				if (self[a] - point) * (self[b] - point) <= T::ZERO {
 					neg_count += 1;
 				}
 			}
@@ -36,39 +38,80 @@ impl Triangle {
 	}
 }
 
+impl<T: Scalar> Bounded<T> for Triangle<T> {
+	fn bounds(&self) -> Aabb<T> {
+		Aabb::from_triangle(self)
+	}
+}
+
 use ray_tracing::*;
 
-impl RayTarget for Triangle {
-	fn hit_info(&self, ray: &Ray) -> Option<HitInfo> {
-		let n = self.normal();
-		let a = (self[0] - ray.start) * n;
-		let b = ray.direction * n;
-		if b == 0.0 {
+impl<T: Scalar> Triangle<T> {
+	/// get the full info of a Ray hit, ignoring hits on the back side of the Triangle
+	///
+	/// The front side is the one that the [normal](#method.normal) points to, which is the side
+	/// where the corners appear counter-clockwise.
+	///
+	/// returns None if the Ray does not hit the front side of the Triangle
+	pub fn hit_info_culled(&self, ray: &Ray<T>) -> Option<HitInfo<T>> {
+		self.moller_trumbore(ray, true)
+	}
+	/// Intersects the Ray with the Triangle using the [Möller–Trumbore algorithm](https://en.wikipedia.org/wiki/M%C3%B6ller%E2%80%93Trumbore_intersection_algorithm)
+	fn moller_trumbore(&self, ray: &Ray<T>, cull_back_faces: bool) -> Option<HitInfo<T>> {
+		let edge1 = self[1] - self[0];
+		let edge2 = self[2] - self[0];
+		let p = ray.direction.cross(edge2);
+		// det is negative if the Ray hits the back side and 0 if it is parallel to the Triangle
+		let det = edge1 * p;
+		if det == T::ZERO || (cull_back_faces && det < T::ZERO) {
 			return None;
 		}
-		let point = ray.start + ray.direction * a / b;
-		if self.contains(point) {
-			Some(HitInfo {
-				point,
-				normal: n,
-				..Default::default()
-			})
-		} else {
-			None
+		let inv_det = T::ONE / det;
+
+		let s = ray.start - self[0];
+		let u = s * p * inv_det;
+		if u < T::ZERO || u > T::ONE {
+			return None;
 		}
+		let q = s.cross(edge1);
+		let v = ray.direction * q * inv_det;
+		if v < T::ZERO || u + v > T::ONE {
+			return None;
+		}
+		let distance = edge2 * q * inv_det;
+		if !ray.accepts(distance) || !distance.is_finite() {
+			return None;
+		}
+		Some(HitInfo {
+			point: ray.at(distance),
+			distance,
+			normal: edge1.cross(edge2).norm(),
+			barycentric: Some(Vec3 {
+				x: T::ONE - u - v,
+				y: u,
+				z: v,
+			}),
+			..Default::default()
+		})
+	}
+}
+
+impl<T: Scalar> RayTarget<T> for Triangle<T> {
+	fn hit_info(&self, ray: &Ray<T>) -> Option<HitInfo<T>> {
+		self.moller_trumbore(ray, false)
 	}
 }
 
 use std::ops::*;
 
-impl Index<usize> for Triangle {
-	type Output = Vector;
-	fn index(&self, index: usize) -> &Vector {
+impl<T: Scalar> Index<usize> for Triangle<T> {
+	type Output = Vec3<T>;
+	fn index(&self, index: usize) -> &Vec3<T> {
 		&self.corners[index]
 	}
 }
-impl IndexMut<usize> for Triangle {
-	fn index_mut(&mut self, index: usize) -> &mut Vector {
+impl<T: Scalar> IndexMut<usize> for Triangle<T> {
+	fn index_mut(&mut self, index: usize) -> &mut Vec3<T> {
 		&mut self.corners[index]
 	}
 }
@@ -76,6 +119,7 @@ impl IndexMut<usize> for Triangle {
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use vector::{DVector, Vector};
 
 	#[test]
 	fn triangle_new() {
@@ -100,6 +144,7 @@ mod tests {
 	}
 
 	#[test]
+	#[allow(clippy::legacy_numeric_constants)]
 	fn triangle_area() {
 		let a = Vector::from((2.0, 1.0, 0.0));
 		let b = Vector::from((1.0, 3.0, 2.0));
@@ -108,4 +153,71 @@ mod tests {
 		assert!((t.area() - 1.5).abs() <= std::f32::EPSILON);
 	}
 
+	#[test]
+	fn triangle_f64() {
+		let a = DVector::from((2.0, 1.0, 0.0));
+		let b = DVector::from((1.0, 3.0, 2.0));
+		let c = DVector::from((1.0, 1.0, 1.0));
+		let t = Triangle::new(a, b, c);
+		assert!((t.area() - 1.5).abs() <= f64::EPSILON);
+	}
+
+	#[test]
+	fn triangle_hit_info() {
+		let t = Triangle::new(
+			Vector::from((0.0, 0.0, 0.0)),
+			Vector::from((4.0, 0.0, 0.0)),
+			Vector::from((0.0, 4.0, 0.0)),
+		);
+		let ray = Ray::new(Vector::from((1.0, 2.0, 3.0)), Vector::from((0.0, 0.0, -1.0)));
+		let hit = t.hit_info(&ray).unwrap();
+		assert_eq!(hit.point, Vector::from((1.0, 2.0, 0.0)));
+		assert!((hit.distance - 3.0).abs() <= f32::EPSILON);
+		assert_eq!(hit.normal, Vector::from((0.0, 0.0, 1.0)));
+		assert_eq!(hit.barycentric, Some(Vector::from((0.25, 0.25, 0.5))));
+
+		// the Triangle is behind the Ray
+		let away = Ray::new(ray.start, -ray.direction);
+		assert!(t.hit_info(&away).is_none());
+		// the Ray passes the Triangle
+		assert!(!t.hits(&Ray::new(Vector::from((3.0, 3.0, 3.0)), ray.direction)));
+		// the Ray is parallel to the Triangle
+		assert!(!t.hits(&Ray::new(Vector::from((-1.0, 1.0, 0.0)), Vector::from((1.0, 0.0, 0.0)))));
+	}
+
+	#[test]
+	fn triangle_hit_info_culled() {
+		let t = Triangle::new(
+			Vector::from((0.0, 0.0, 0.0)),
+			Vector::from((4.0, 0.0, 0.0)),
+			Vector::from((0.0, 4.0, 0.0)),
+		);
+		let front = Ray::new(Vector::from((1.0, 1.0, 1.0)), Vector::from((0.0, 0.0, -1.0)));
+		let back = Ray::new(Vector::from((1.0, 1.0, -1.0)), Vector::from((0.0, 0.0, 1.0)));
+		assert!(t.hit_info_culled(&front).is_some());
+		assert!(t.hit_info_culled(&back).is_none());
+		assert!(t.hit_info(&back).is_some());
+	}
+
+	#[test]
+	fn triangle_ray_interval() {
+		let t = Triangle::new(
+			Vector::from((0.0, 0.0, 0.0)),
+			Vector::from((4.0, 0.0, 0.0)),
+			Vector::from((0.0, 4.0, 0.0)),
+		);
+		let ray = Ray::new(Vector::from((1.0, 1.0, 2.0)), Vector::from((0.0, 0.0, -1.0)));
+		assert!(t.hits(&ray.with_interval(1.0, 3.0)));
+		assert!(!t.hits(&ray.with_interval(0.0, 1.5)));
+		assert!(!t.hits(&ray.with_interval(2.5, 10.0)));
+		assert!(!t.hits(&Ray::segment(ray.start, Vector::from((1.0, 1.0, 0.5)))));
+		assert!(t.hits(&Ray::segment(ray.start, Vector::from((1.0, 1.0, -0.5)))));
+
+		// the reflected Ray does not hit the Triangle it was reflected from
+		let hit = t.hit_info(&Ray::new(ray.start, Vector::from((0.1, 0.3, -1.0)))).unwrap();
+		let reflected = Ray::new(ray.start, Vector::from((0.1, 0.3, -1.0))).reflect(&hit);
+		assert!(reflected.direction.z > 0.0);
+		assert!(!t.hits(&reflected));
+		assert!(!t.hits(&Ray::new(hit.point, -reflected.direction).with_interval(reflected.t_min, 1.0)));
+	}
 }
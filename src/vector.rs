@@ -1,30 +1,41 @@
-/// A 3-Dimensional Vector with x, y, z Components as f32
+use vector2::Vec2;
+use vector4::Vec4;
+use Scalar;
+
+/// A 3-Dimensional Vector with x, y, z Components of the Scalar Type `T`
+///
+/// Most code should use the [`Vector`](type.Vector.html) (`f32`) or [`DVector`](type.DVector.html) (`f64`) aliases.
 #[derive(Clone, Copy, Debug, Default)]
-pub struct Vector {
+pub struct Vec3<T: Scalar> {
 	/// the x Component
-	pub x: f32,
+	pub x: T,
 	/// the y Component
-	pub y: f32,
+	pub y: T,
 	/// the z Component
-	pub z: f32,
+	pub z: T,
 }
 
-impl Vector {
+/// A 3-Dimensional Vector with `f32` Components
+pub type Vector = Vec3<f32>;
+/// A 3-Dimensional Vector with `f64` Components
+pub type DVector = Vec3<f64>;
+
+impl<T: Scalar> Vec3<T> {
 	/// Creates a new Vector with x, y, z Components set to 0.0
-	pub fn new() -> Vector {
+	pub fn new() -> Vec3<T> {
 		Default::default()
 	}
 	/// Returns a new Vector with the x Component set to `x`
-	pub fn x(self, x: f32) -> Vector {
-		Vector { x, ..self }
+	pub fn x(self, x: T) -> Vec3<T> {
+		Vec3 { x, ..self }
 	}
 	/// Returns a new Vector with the y Component set to `y`
-	pub fn y(self, y: f32) -> Vector {
-		Vector { y, ..self }
+	pub fn y(self, y: T) -> Vec3<T> {
+		Vec3 { y, ..self }
 	}
 	/// Returns a new Vector with the z Component set to `z`
-	pub fn z(self, z: f32) -> Vector {
-		Vector { z, ..self }
+	pub fn z(self, z: T) -> Vec3<T> {
+		Vec3 { z, ..self }
 	}
 	/// Returns the [cross product](https://en.wikipedia.org/wiki/Cross_product) of two Vectors
 	///
@@ -35,8 +46,8 @@ impl Vector {
 	/// y = a.z * b.x - a.x * b.z
 	/// z = a.x * b.y - a.y * b.x
 	/// ```
-	pub fn cross(self, rhs: Vector) -> Vector {
-		Vector {
+	pub fn cross(self, rhs: Vec3<T>) -> Vec3<T> {
+		Vec3 {
 			x: self.y * rhs.z - self.z * rhs.y,
 			y: self.z * rhs.x - self.x * rhs.z,
 			z: self.x * rhs.y - self.y * rhs.x,
@@ -50,13 +61,13 @@ impl Vector {
 	/// ```
 	///
 	/// this is the same method as [len_sq](#method.len_sq), except that it calculates the square root of the Result
-	pub fn length(self) -> f32 {
+	pub fn length(self) -> T {
 		self.length_sq().sqrt()
 	}
 	/// Calculates the squared length of the Vector
 	///
 	/// this is the same method as [len](#method.len), except that it does not calculate the square root of the Result, making it slightly faster
-	pub fn length_sq(self) -> f32 {
+	pub fn length_sq(self) -> T {
 		self * self
 	}
 	/// Returns a normalized Vector pointing in the same direction as `self`
@@ -67,7 +78,7 @@ impl Vector {
 	/// let v = Vector { x: 5.0, y: 1.0, z: -3.5 };
 	/// assert!((v.norm().length() - 1.0).abs() <= std::f32::EPSILON);
 	/// ```
-	pub fn norm(self) -> Vector {
+	pub fn norm(self) -> Vec3<T> {
 		self / self.length()
 	}
 	/// Calculates the angle between two Vectors in Radians
@@ -85,38 +96,72 @@ impl Vector {
 	/// # let b = Vector { x: 0.0, y: 1.0, z: 0.0 };
 	/// assert_eq!(a.angle(b), b.angle(a));
 	/// ```
-	pub fn angle(self, other: Vector) -> f32 {
+	pub fn angle(self, other: Vec3<T>) -> T {
 		(self * other / (self.length() * other.length())).acos()
 	}
+	/// Returns the component-wise minimum of two Vectors
+	pub fn min(self, other: Vec3<T>) -> Vec3<T> {
+		Vec3 {
+			x: self.x.min(other.x),
+			y: self.y.min(other.y),
+			z: self.z.min(other.z),
+		}
+	}
+	/// Returns the component-wise maximum of two Vectors
+	pub fn max(self, other: Vec3<T>) -> Vec3<T> {
+		Vec3 {
+			x: self.x.max(other.x),
+			y: self.y.max(other.y),
+			z: self.z.max(other.z),
+		}
+	}
+	/// Returns a 2D Vector with the x and y Components of `self`, dropping z
+	pub fn truncate(self) -> Vec2<T> {
+		Vec2 {
+			x: self.x,
+			y: self.y,
+		}
+	}
+	/// Returns a 4D Vector with the x, y and z Components of `self` and the given `w` Component
+	///
+	/// Use `w = 1` for Points and `w = 0` for directions
+	pub fn extend(self, w: T) -> Vec4<T> {
+		Vec4 {
+			x: self.x,
+			y: self.y,
+			z: self.z,
+			w,
+		}
+	}
 }
 
-impl From<[f32; 3]> for Vector {
-	fn from(src: [f32; 3]) -> Vector {
-		Vector {
+impl<T: Scalar> From<[T; 3]> for Vec3<T> {
+	fn from(src: [T; 3]) -> Vec3<T> {
+		Vec3 {
 			x: src[0],
 			y: src[1],
 			z: src[2],
 		}
 	}
 }
-impl From<&[f32]> for Vector {
-	fn from(src: &[f32]) -> Vector {
+impl<T: Scalar> From<&[T]> for Vec3<T> {
+	fn from(src: &[T]) -> Vec3<T> {
 		assert_eq!(
 			src.len(),
 			3,
-			"Vector::from(&[f32]) Input slice has incorrect length: {} given, 3 expected",
+			"Vector::from(&[T]) Input slice has incorrect length: {} given, 3 expected",
 			src.len()
 		);
-		Vector {
+		Vec3 {
 			x: src[0],
 			y: src[1],
 			z: src[2],
 		}
 	}
 }
-impl From<(f32, f32, f32)> for Vector {
-	fn from(src: (f32, f32, f32)) -> Vector {
-		Vector {
+impl<T: Scalar> From<(T, T, T)> for Vec3<T> {
+	fn from(src: (T, T, T)) -> Vec3<T> {
+		Vec3 {
 			x: src.0,
 			y: src.1,
 			z: src.2,
@@ -126,93 +171,93 @@ impl From<(f32, f32, f32)> for Vector {
 
 use std::ops::*;
 
-impl Add for Vector {
-	type Output = Vector;
-	fn add(self, rhs: Vector) -> Vector {
-		Vector {
+impl<T: Scalar> Add for Vec3<T> {
+	type Output = Vec3<T>;
+	fn add(self, rhs: Vec3<T>) -> Vec3<T> {
+		Vec3 {
 			x: self.x + rhs.x,
 			y: self.y + rhs.y,
 			z: self.z + rhs.z,
 		}
 	}
 }
-impl AddAssign for Vector {
-	fn add_assign(&mut self, rhs: Vector) {
+impl<T: Scalar> AddAssign for Vec3<T> {
+	fn add_assign(&mut self, rhs: Vec3<T>) {
 		*self = *self + rhs;
 	}
 }
-impl Sub for Vector {
-	type Output = Vector;
-	fn sub(self, rhs: Vector) -> Vector {
-		Vector {
+impl<T: Scalar> Sub for Vec3<T> {
+	type Output = Vec3<T>;
+	fn sub(self, rhs: Vec3<T>) -> Vec3<T> {
+		Vec3 {
 			x: self.x - rhs.x,
 			y: self.y - rhs.y,
 			z: self.z - rhs.z,
 		}
 	}
 }
-impl SubAssign for Vector {
-	fn sub_assign(&mut self, rhs: Vector) {
+impl<T: Scalar> SubAssign for Vec3<T> {
+	fn sub_assign(&mut self, rhs: Vec3<T>) {
 		*self = *self - rhs;
 	}
 }
-impl Mul for Vector {
-	type Output = f32;
-	fn mul(self, rhs: Vector) -> f32 {
+impl<T: Scalar> Mul for Vec3<T> {
+	type Output = T;
+	fn mul(self, rhs: Vec3<T>) -> T {
 		self.x * rhs.x + self.y * rhs.y + self.z * rhs.z
 	}
 }
 
-impl Mul<f32> for Vector {
-	type Output = Vector;
-	fn mul(self, rhs: f32) -> Vector {
-		Vector {
+impl<T: Scalar> Mul<T> for Vec3<T> {
+	type Output = Vec3<T>;
+	fn mul(self, rhs: T) -> Vec3<T> {
+		Vec3 {
 			x: self.x * rhs,
 			y: self.y * rhs,
 			z: self.z * rhs,
 		}
 	}
 }
-impl MulAssign<f32> for Vector {
-	fn mul_assign(&mut self, rhs: f32) {
+impl<T: Scalar> MulAssign<T> for Vec3<T> {
+	fn mul_assign(&mut self, rhs: T) {
 		*self = *self * rhs;
 	}
 }
 
-impl Div<f32> for Vector {
-	type Output = Vector;
-	fn div(self, rhs: f32) -> Vector {
-		Vector {
+impl<T: Scalar> Div<T> for Vec3<T> {
+	type Output = Vec3<T>;
+	fn div(self, rhs: T) -> Vec3<T> {
+		Vec3 {
 			x: self.x / rhs,
 			y: self.y / rhs,
 			z: self.z / rhs,
 		}
 	}
 }
-impl DivAssign<f32> for Vector {
-	fn div_assign(&mut self, rhs: f32) {
+impl<T: Scalar> DivAssign<T> for Vec3<T> {
+	fn div_assign(&mut self, rhs: T) {
 		*self = *self / rhs;
 	}
 }
 
-use super::Matrix;
+use matrix::Mat4;
 
-impl Mul<Matrix> for Vector {
-	type Output = Vector;
-	fn mul(self, rhs: Matrix) -> Vector {
+impl<T: Scalar> Mul<Mat4<T>> for Vec3<T> {
+	type Output = Vec3<T>;
+	fn mul(self, rhs: Mat4<T>) -> Vec3<T> {
 		rhs * self
 	}
 }
-impl MulAssign<Matrix> for Vector {
-	fn mul_assign(&mut self, rhs: Matrix) {
+impl<T: Scalar> MulAssign<Mat4<T>> for Vec3<T> {
+	fn mul_assign(&mut self, rhs: Mat4<T>) {
 		*self = rhs * *self;
 	}
 }
 
-impl Neg for Vector {
-	type Output = Vector;
-	fn neg(self) -> Vector {
-		Vector {
+impl<T: Scalar> Neg for Vec3<T> {
+	type Output = Vec3<T>;
+	fn neg(self) -> Vec3<T> {
+		Vec3 {
 			x: -self.x,
 			y: -self.y,
 			z: -self.z,
@@ -220,24 +265,24 @@ impl Neg for Vector {
 	}
 }
 
-impl PartialEq for Vector {
-	fn eq(&self, rhs: &Vector) -> bool {
-		use std::f32::EPSILON as epsilon;
+impl<T: Scalar> PartialEq for Vec3<T> {
+	fn eq(&self, rhs: &Vec3<T>) -> bool {
+		let epsilon = T::EPSILON;
 		(self.x - rhs.x).abs() <= epsilon
 			&& (self.y - rhs.y).abs() <= epsilon
 			&& (self.z - rhs.z).abs() <= epsilon
 	}
 }
 
-impl std::iter::Sum for Vector {
+impl<T: Scalar> std::iter::Sum for Vec3<T> {
 	fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
-		iter.fold(Vector::new(), |a, b| a + b)
+		iter.fold(Vec3::new(), |a, b| a + b)
 	}
 }
 
-impl Index<usize> for Vector {
-	type Output = f32;
-	fn index(&self, index: usize) -> &f32 {
+impl<T: Scalar> Index<usize> for Vec3<T> {
+	type Output = T;
+	fn index(&self, index: usize) -> &T {
 		match index {
 			0 => &self.x,
 			1 => &self.y,
@@ -246,8 +291,8 @@ impl Index<usize> for Vector {
 		}
 	}
 }
-impl IndexMut<usize> for Vector {
-	fn index_mut(&mut self, index: usize) -> &mut f32 {
+impl<T: Scalar> IndexMut<usize> for Vec3<T> {
+	fn index_mut(&mut self, index: usize) -> &mut T {
 		match index {
 			0 => &mut self.x,
 			1 => &mut self.y,
@@ -259,7 +304,7 @@ impl IndexMut<usize> for Vector {
 
 use std::fmt::{Display, Formatter, Result};
 
-impl Display for Vector {
+impl<T: Scalar> Display for Vec3<T> {
 	fn fmt(&self, f: &mut Formatter) -> Result {
 		write!(f, "({}, {}, {})", self.x, self.y, self.z,)
 	}
@@ -271,6 +316,7 @@ mod tests {
 	use super::*;
 
 	#[test]
+	#[allow(clippy::legacy_numeric_constants)]
 	fn vector_new() {
 		let v: Vector = Vector::new();
 		assert!((v.x - 0.0).abs() <= std::f32::EPSILON);
@@ -278,4 +324,11 @@ mod tests {
 		assert!((v.z - 0.0).abs() <= std::f32::EPSILON);
 	}
 
+	#[test]
+	fn vector_f64() {
+		let v = DVector::from((3.0, 0.0, 4.0));
+		assert!((v.length() - 5.0).abs() <= f64::EPSILON);
+		assert_eq!(v.norm(), DVector::from((0.6, 0.0, 0.8)));
+	}
+
 }
@@ -0,0 +1,240 @@
+use vector::Vec3;
+use Scalar;
+
+/// A 2-Dimensional Vector with x, y Components of the Scalar Type `T`
+///
+/// Most code should use the [`Vector2`](type.Vector2.html) (`f32`) or [`DVector2`](type.DVector2.html) (`f64`) aliases.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Vec2<T: Scalar> {
+	/// the x Component
+	pub x: T,
+	/// the y Component
+	pub y: T,
+}
+
+/// A 2-Dimensional Vector with `f32` Components
+pub type Vector2 = Vec2<f32>;
+/// A 2-Dimensional Vector with `f64` Components
+pub type DVector2 = Vec2<f64>;
+
+impl<T: Scalar> Vec2<T> {
+	/// Creates a new Vector with x and y Components set to 0.0
+	pub fn new() -> Vec2<T> {
+		Default::default()
+	}
+	/// Returns a new Vector with the x Component set to `x`
+	pub fn x(self, x: T) -> Vec2<T> {
+		Vec2 { x, ..self }
+	}
+	/// Returns a new Vector with the y Component set to `y`
+	pub fn y(self, y: T) -> Vec2<T> {
+		Vec2 { y, ..self }
+	}
+	/// Calculates the length of the Vector
+	pub fn length(self) -> T {
+		self.length_sq().sqrt()
+	}
+	/// Calculates the squared length of the Vector
+	///
+	/// this is the same method as [length](#method.length), except that it does not calculate the square root of the Result, making it slightly faster
+	pub fn length_sq(self) -> T {
+		self * self
+	}
+	/// Returns a normalized Vector pointing in the same direction as `self`
+	pub fn norm(self) -> Vec2<T> {
+		self / self.length()
+	}
+	/// Returns a 3D Vector with the x and y Components of `self` and the given `z` Component
+	pub fn extend(self, z: T) -> Vec3<T> {
+		Vec3 {
+			x: self.x,
+			y: self.y,
+			z,
+		}
+	}
+	/// Returns the 2D cross product (the z Component of the 3D cross product) of two Vectors
+	///
+	/// This is positive if `rhs` is counter-clockwise from `self`
+	pub fn perp_dot(self, rhs: Vec2<T>) -> T {
+		self.x * rhs.y - self.y * rhs.x
+	}
+}
+
+impl<T: Scalar> From<[T; 2]> for Vec2<T> {
+	fn from(src: [T; 2]) -> Vec2<T> {
+		Vec2 {
+			x: src[0],
+			y: src[1],
+		}
+	}
+}
+impl<T: Scalar> From<&[T]> for Vec2<T> {
+	fn from(src: &[T]) -> Vec2<T> {
+		assert_eq!(
+			src.len(),
+			2,
+			"Vector2::from(&[T]) Input slice has incorrect length: {} given, 2 expected",
+			src.len()
+		);
+		Vec2 {
+			x: src[0],
+			y: src[1],
+		}
+	}
+}
+impl<T: Scalar> From<(T, T)> for Vec2<T> {
+	fn from(src: (T, T)) -> Vec2<T> {
+		Vec2 {
+			x: src.0,
+			y: src.1,
+		}
+	}
+}
+
+use std::ops::*;
+
+impl<T: Scalar> Add for Vec2<T> {
+	type Output = Vec2<T>;
+	fn add(self, rhs: Vec2<T>) -> Vec2<T> {
+		Vec2 {
+			x: self.x + rhs.x,
+			y: self.y + rhs.y,
+		}
+	}
+}
+impl<T: Scalar> AddAssign for Vec2<T> {
+	fn add_assign(&mut self, rhs: Vec2<T>) {
+		*self = *self + rhs;
+	}
+}
+impl<T: Scalar> Sub for Vec2<T> {
+	type Output = Vec2<T>;
+	fn sub(self, rhs: Vec2<T>) -> Vec2<T> {
+		Vec2 {
+			x: self.x - rhs.x,
+			y: self.y - rhs.y,
+		}
+	}
+}
+impl<T: Scalar> SubAssign for Vec2<T> {
+	fn sub_assign(&mut self, rhs: Vec2<T>) {
+		*self = *self - rhs;
+	}
+}
+impl<T: Scalar> Mul for Vec2<T> {
+	type Output = T;
+	fn mul(self, rhs: Vec2<T>) -> T {
+		self.x * rhs.x + self.y * rhs.y
+	}
+}
+
+impl<T: Scalar> Mul<T> for Vec2<T> {
+	type Output = Vec2<T>;
+	fn mul(self, rhs: T) -> Vec2<T> {
+		Vec2 {
+			x: self.x * rhs,
+			y: self.y * rhs,
+		}
+	}
+}
+impl<T: Scalar> MulAssign<T> for Vec2<T> {
+	fn mul_assign(&mut self, rhs: T) {
+		*self = *self * rhs;
+	}
+}
+
+impl<T: Scalar> Div<T> for Vec2<T> {
+	type Output = Vec2<T>;
+	fn div(self, rhs: T) -> Vec2<T> {
+		Vec2 {
+			x: self.x / rhs,
+			y: self.y / rhs,
+		}
+	}
+}
+impl<T: Scalar> DivAssign<T> for Vec2<T> {
+	fn div_assign(&mut self, rhs: T) {
+		*self = *self / rhs;
+	}
+}
+
+impl<T: Scalar> Neg for Vec2<T> {
+	type Output = Vec2<T>;
+	fn neg(self) -> Vec2<T> {
+		Vec2 {
+			x: -self.x,
+			y: -self.y,
+		}
+	}
+}
+
+impl<T: Scalar> PartialEq for Vec2<T> {
+	fn eq(&self, rhs: &Vec2<T>) -> bool {
+		let epsilon = T::EPSILON;
+		(self.x - rhs.x).abs() <= epsilon
+			&& (self.y - rhs.y).abs() <= epsilon
+	}
+}
+
+impl<T: Scalar> std::iter::Sum for Vec2<T> {
+	fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+		iter.fold(Vec2::new(), |a, b| a + b)
+	}
+}
+
+impl<T: Scalar> Index<usize> for Vec2<T> {
+	type Output = T;
+	fn index(&self, index: usize) -> &T {
+		match index {
+			0 => &self.x,
+			1 => &self.y,
+			_ => panic!("Vector2 index out of Range: {} given, max 1", index),
+		}
+	}
+}
+impl<T: Scalar> IndexMut<usize> for Vec2<T> {
+	fn index_mut(&mut self, index: usize) -> &mut T {
+		match index {
+			0 => &mut self.x,
+			1 => &mut self.y,
+			_ => panic!("Vector2 index out of Range: {} given, max 1", index),
+		}
+	}
+}
+
+use std::fmt::{Display, Formatter, Result};
+
+impl<T: Scalar> Display for Vec2<T> {
+	fn fmt(&self, f: &mut Formatter) -> Result {
+		write!(f, "({}, {})", self.x, self.y)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn vector2_ops() {
+		let a = Vector2::from((3.0, 4.0));
+		let b = Vector2::from([1.0, -1.0]);
+		assert!((a.length() - 5.0).abs() <= f32::EPSILON);
+		assert_eq!(a + b, Vector2::from((4.0, 3.0)));
+		assert_eq!(a - b, Vector2::from((2.0, 5.0)));
+		assert_eq!(-a * 2.0, Vector2::from((-6.0, -8.0)));
+		assert_eq!(a / 2.0, Vector2::from((1.5, 2.0)));
+		assert_eq!(a * b, -1.0);
+		assert_eq!(a.perp_dot(b), -7.0);
+		assert_eq!(vec![a, b].into_iter().sum::<Vector2>(), a + b);
+		assert_eq!(a[1], 4.0);
+		assert_eq!(format!("{}", a), "(3, 4)");
+	}
+
+	#[test]
+	fn vector2_extend() {
+		let v = Vector2::from((1.0, 2.0)).extend(3.0);
+		assert_eq!(v, Vec3::from((1.0, 2.0, 3.0)));
+		assert_eq!(v.truncate(), Vector2::from((1.0, 2.0)));
+	}
+
+}
@@ -0,0 +1,302 @@
+use vector::Vec3;
+use Scalar;
+
+/// A 4-Dimensional Vector with x, y, z, w Components of the Scalar Type `T`
+///
+/// Most code should use the [`Vector4`](type.Vector4.html) (`f32`) or [`DVector4`](type.DVector4.html) (`f64`) aliases.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Vec4<T: Scalar> {
+	/// the x Component
+	pub x: T,
+	/// the y Component
+	pub y: T,
+	/// the z Component
+	pub z: T,
+	/// the w Component
+	pub w: T,
+}
+
+/// A 4-Dimensional Vector with `f32` Components
+pub type Vector4 = Vec4<f32>;
+/// A 4-Dimensional Vector with `f64` Components
+pub type DVector4 = Vec4<f64>;
+
+impl<T: Scalar> Vec4<T> {
+	/// Creates a new Vector with x, y, z and w Components set to 0.0
+	pub fn new() -> Vec4<T> {
+		Default::default()
+	}
+	/// Returns a new Vector with the x Component set to `x`
+	pub fn x(self, x: T) -> Vec4<T> {
+		Vec4 { x, ..self }
+	}
+	/// Returns a new Vector with the y Component set to `y`
+	pub fn y(self, y: T) -> Vec4<T> {
+		Vec4 { y, ..self }
+	}
+	/// Returns a new Vector with the z Component set to `z`
+	pub fn z(self, z: T) -> Vec4<T> {
+		Vec4 { z, ..self }
+	}
+	/// Returns a new Vector with the w Component set to `w`
+	pub fn w(self, w: T) -> Vec4<T> {
+		Vec4 { w, ..self }
+	}
+	/// Calculates the length of the Vector
+	pub fn length(self) -> T {
+		self.length_sq().sqrt()
+	}
+	/// Calculates the squared length of the Vector
+	///
+	/// this is the same method as [length](#method.length), except that it does not calculate the square root of the Result, making it slightly faster
+	pub fn length_sq(self) -> T {
+		self * self
+	}
+	/// Returns a normalized Vector pointing in the same direction as `self`
+	pub fn norm(self) -> Vec4<T> {
+		self / self.length()
+	}
+	/// Returns a 3D Vector with the x, y and z Components of `self`, dropping w
+	///
+	/// No perspective division happens, see [project](#method.project) for that
+	pub fn truncate(self) -> Vec3<T> {
+		Vec3 {
+			x: self.x,
+			y: self.y,
+			z: self.z,
+		}
+	}
+	/// Returns the 3D Point represented by these homogeneous coordinates, by dividing by w
+	///
+	/// returns None if w is 0, since the Point is at infinity
+	pub fn project(self) -> Option<Vec3<T>> {
+		if self.w == T::ZERO {
+			None
+		} else {
+			Some(self.truncate() / self.w)
+		}
+	}
+}
+
+impl<T: Scalar> From<[T; 4]> for Vec4<T> {
+	fn from(src: [T; 4]) -> Vec4<T> {
+		Vec4 {
+			x: src[0],
+			y: src[1],
+			z: src[2],
+			w: src[3],
+		}
+	}
+}
+impl<T: Scalar> From<&[T]> for Vec4<T> {
+	fn from(src: &[T]) -> Vec4<T> {
+		assert_eq!(
+			src.len(),
+			4,
+			"Vector4::from(&[T]) Input slice has incorrect length: {} given, 4 expected",
+			src.len()
+		);
+		Vec4 {
+			x: src[0],
+			y: src[1],
+			z: src[2],
+			w: src[3],
+		}
+	}
+}
+impl<T: Scalar> From<(T, T, T, T)> for Vec4<T> {
+	fn from(src: (T, T, T, T)) -> Vec4<T> {
+		Vec4 {
+			x: src.0,
+			y: src.1,
+			z: src.2,
+			w: src.3,
+		}
+	}
+}
+
+use std::ops::*;
+
+impl<T: Scalar> Add for Vec4<T> {
+	type Output = Vec4<T>;
+	fn add(self, rhs: Vec4<T>) -> Vec4<T> {
+		Vec4 {
+			x: self.x + rhs.x,
+			y: self.y + rhs.y,
+			z: self.z + rhs.z,
+			w: self.w + rhs.w,
+		}
+	}
+}
+impl<T: Scalar> AddAssign for Vec4<T> {
+	fn add_assign(&mut self, rhs: Vec4<T>) {
+		*self = *self + rhs;
+	}
+}
+impl<T: Scalar> Sub for Vec4<T> {
+	type Output = Vec4<T>;
+	fn sub(self, rhs: Vec4<T>) -> Vec4<T> {
+		Vec4 {
+			x: self.x - rhs.x,
+			y: self.y - rhs.y,
+			z: self.z - rhs.z,
+			w: self.w - rhs.w,
+		}
+	}
+}
+impl<T: Scalar> SubAssign for Vec4<T> {
+	fn sub_assign(&mut self, rhs: Vec4<T>) {
+		*self = *self - rhs;
+	}
+}
+impl<T: Scalar> Mul for Vec4<T> {
+	type Output = T;
+	fn mul(self, rhs: Vec4<T>) -> T {
+		self.x * rhs.x + self.y * rhs.y + self.z * rhs.z + self.w * rhs.w
+	}
+}
+
+impl<T: Scalar> Mul<T> for Vec4<T> {
+	type Output = Vec4<T>;
+	fn mul(self, rhs: T) -> Vec4<T> {
+		Vec4 {
+			x: self.x * rhs,
+			y: self.y * rhs,
+			z: self.z * rhs,
+			w: self.w * rhs,
+		}
+	}
+}
+impl<T: Scalar> MulAssign<T> for Vec4<T> {
+	fn mul_assign(&mut self, rhs: T) {
+		*self = *self * rhs;
+	}
+}
+
+impl<T: Scalar> Div<T> for Vec4<T> {
+	type Output = Vec4<T>;
+	fn div(self, rhs: T) -> Vec4<T> {
+		Vec4 {
+			x: self.x / rhs,
+			y: self.y / rhs,
+			z: self.z / rhs,
+			w: self.w / rhs,
+		}
+	}
+}
+impl<T: Scalar> DivAssign<T> for Vec4<T> {
+	fn div_assign(&mut self, rhs: T) {
+		*self = *self / rhs;
+	}
+}
+
+use matrix::Mat4;
+
+impl<T: Scalar> Mul<Vec4<T>> for Mat4<T> {
+	type Output = Vec4<T>;
+	/// Multiplies the Matrix with the homogeneous Vector, without any perspective division
+	fn mul(self, rhs: Vec4<T>) -> Vec4<T> {
+		let mut out = Vec4::new();
+		for i in 0..4 {
+			for j in 0..4 {
+				out[i] += self[i][j] * rhs[j];
+			}
+		}
+		out
+	}
+}
+
+impl<T: Scalar> Neg for Vec4<T> {
+	type Output = Vec4<T>;
+	fn neg(self) -> Vec4<T> {
+		Vec4 {
+			x: -self.x,
+			y: -self.y,
+			z: -self.z,
+			w: -self.w,
+		}
+	}
+}
+
+impl<T: Scalar> PartialEq for Vec4<T> {
+	fn eq(&self, rhs: &Vec4<T>) -> bool {
+		let epsilon = T::EPSILON;
+		(self.x - rhs.x).abs() <= epsilon
+			&& (self.y - rhs.y).abs() <= epsilon
+			&& (self.z - rhs.z).abs() <= epsilon
+			&& (self.w - rhs.w).abs() <= epsilon
+	}
+}
+
+impl<T: Scalar> std::iter::Sum for Vec4<T> {
+	fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+		iter.fold(Vec4::new(), |a, b| a + b)
+	}
+}
+
+impl<T: Scalar> Index<usize> for Vec4<T> {
+	type Output = T;
+	fn index(&self, index: usize) -> &T {
+		match index {
+			0 => &self.x,
+			1 => &self.y,
+			2 => &self.z,
+			3 => &self.w,
+			_ => panic!("Vector4 index out of Range: {} given, max 3", index),
+		}
+	}
+}
+impl<T: Scalar> IndexMut<usize> for Vec4<T> {
+	fn index_mut(&mut self, index: usize) -> &mut T {
+		match index {
+			0 => &mut self.x,
+			1 => &mut self.y,
+			2 => &mut self.z,
+			3 => &mut self.w,
+			_ => panic!("Vector4 index out of Range: {} given, max 3", index),
+		}
+	}
+}
+
+use std::fmt::{Display, Formatter, Result};
+
+impl<T: Scalar> Display for Vec4<T> {
+	fn fmt(&self, f: &mut Formatter) -> Result {
+		write!(f, "({}, {}, {}, {})", self.x, self.y, self.z, self.w)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn vector4_ops() {
+		let a = Vector4::from((1.0, 2.0, 2.0, 4.0));
+		let b = Vector4::from([1.0, 0.0, -1.0, 0.5]);
+		assert!((a.length() - 5.0).abs() <= f32::EPSILON);
+		assert_eq!(a + b, Vector4::from((2.0, 2.0, 1.0, 4.5)));
+		assert_eq!(a - b, Vector4::from((0.0, 2.0, 3.0, 3.5)));
+		assert_eq!(-a / 2.0, Vector4::from((-0.5, -1.0, -1.0, -2.0)));
+		assert_eq!(a * b, 1.0);
+		assert_eq!(a[3], 4.0);
+		assert_eq!(format!("{}", a), "(1, 2, 2, 4)");
+	}
+
+	#[test]
+	fn vector4_truncate() {
+		let v = Vec3::from((2.0, 4.0, 6.0)).extend(2.0);
+		assert_eq!(v, Vector4::from((2.0, 4.0, 6.0, 2.0)));
+		assert_eq!(v.truncate(), Vec3::from((2.0, 4.0, 6.0)));
+		assert_eq!(v.project(), Some(Vec3::from((1.0, 2.0, 3.0))));
+		assert_eq!(v.w(0.0).project(), None);
+	}
+
+	#[test]
+	fn vector4_matrix() {
+		let m = Mat4::translate(Vec3::from((1.0, 2.0, 3.0)));
+		assert_eq!(m * Vector4::from((1.0, 1.0, 1.0, 0.0)), Vector4::from((1.0, 1.0, 1.0, 0.0)));
+		assert_eq!(m * Vector4::from((1.0, 1.0, 1.0, 2.0)), Vector4::from((3.0, 5.0, 7.0, 2.0)));
+	}
+
+}